@@ -0,0 +1,33 @@
+//! Compares reading a 192 bytes array one byte at a time, as serde does by default,
+//! to reading it all at once through `ucpack::byte_array`.
+//!
+//! Run with `cargo bench --bench byte_array`.
+
+use std::{hint::black_box, time::Instant};
+
+use serde::{Deserialize, Serialize};
+use ucpack::UcPack;
+
+const ITERATIONS: u32 = 100_000;
+
+#[derive(Serialize, Deserialize)]
+struct Bulk(#[serde(with = "ucpack::byte_array")] [u8; 192]);
+
+type PerByte = [[u8; 32]; 6];
+
+fn bench<T: for<'a> Deserialize<'a>>(name: &str, ucpack: &UcPack, frame: &[u8]) {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(ucpack.deserialize_slice::<T>(black_box(frame)).unwrap());
+    }
+
+    println!("{name}: {:?}/iter", start.elapsed() / ITERATIONS);
+}
+
+fn main() {
+    let ucpack = UcPack::default();
+    let frame = ucpack.serialize_vec(&Bulk([0xAA; 192])).unwrap();
+
+    bench::<PerByte>("per byte", &ucpack, &frame);
+    bench::<Bulk>("byte_array", &ucpack, &frame);
+}
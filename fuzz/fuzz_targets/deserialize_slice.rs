@@ -0,0 +1,31 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use serde::Deserialize;
+use ucpack::{fuzz::RawFrame, UcPack};
+
+#[allow(dead_code)]
+#[derive(Deserialize, Debug)]
+enum Command {
+    Stop,
+    Move { left: i16, right: i16 },
+    Led(u8, bool),
+}
+
+#[allow(dead_code)]
+#[derive(Deserialize, Debug)]
+struct Message<'a> {
+    id: u16,
+    name: &'a str,
+    ratio: f32,
+    command: Command,
+}
+
+fuzz_target!(|frame: RawFrame| {
+    let RawFrame(frame) = frame;
+
+    for ucpack in [UcPack::default(), UcPack::default().with_tagged(true)] {
+        let _ = ucpack.deserialize_slice::<Message>(&frame);
+        let _ = ucpack.deserialize_slice::<(u8, u16, f32)>(&frame);
+    }
+});
@@ -0,0 +1,454 @@
+//! Async counterparts of the [buffer](crate::buffer) traits and of the (de)serializers,
+//! for async embedded frameworks such as Embassy.
+//!
+//! `serde` itself is synchronous, so values are still (de)serialized through a stack
+//! buffer of 255 bytes (the longest payload of a classic frame): only the I/O is async.
+//!
+//! Every [WriteBuffer] and [ReadBuffer] is an async buffer as well, never suspending.
+
+#![allow(async_fn_in_trait)] // single threaded executors don't need the futures to be Send
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    buffer::{ReadBuffer, ScratchCursor, SliceCursor, WriteBuffer},
+    de::Deserializer,
+    ser::Serializer,
+    UcPackError,
+};
+
+/// Size of the stack buffer values are (de)serialized through.
+const SCRATCH_SIZE: usize = u8::MAX as usize;
+
+/// An asynchronously writeable buffer, see [WriteBuffer].
+pub trait AsyncWriteBuffer {
+    async fn push_slice(&mut self, bf: &[u8]) -> Result<(), UcPackError>;
+
+    #[inline]
+    async fn push_u8(&mut self, byte: u8) -> Result<(), UcPackError> {
+        self.push_slice(&[byte]).await
+    }
+}
+
+/// An asynchronously readable buffer, see [ReadBuffer].
+///
+/// Values are deserialized from a copy of the data, so `'de` only ties
+/// the buffer to its [ReadBuffer] counterpart.
+pub trait AsyncReadBuffer<'de> {
+    // reads N bytes from the buffer, advancing its internal state, returning a
+    // byte array of N bytes
+    async fn read_n<const N: usize>(&mut self) -> Result<[u8; N], UcPackError>;
+
+    #[inline]
+    async fn read_u8(&mut self) -> Result<u8, UcPackError> {
+        self.read_n().await.map(|[a]| a)
+    }
+
+    /// Fills `out` with the next `out.len()` bytes of the buffer, advancing its internal state.
+    #[inline]
+    async fn read_slice(&mut self, out: &mut [u8]) -> Result<(), UcPackError> {
+        for byte in out {
+            *byte = self.read_u8().await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: WriteBuffer + ?Sized> AsyncWriteBuffer for T {
+    #[inline]
+    async fn push_slice(&mut self, bf: &[u8]) -> Result<(), UcPackError> {
+        WriteBuffer::push_slice(self, bf)
+    }
+
+    #[inline]
+    async fn push_u8(&mut self, byte: u8) -> Result<(), UcPackError> {
+        WriteBuffer::push_u8(self, byte)
+    }
+}
+
+impl<'de, T: ReadBuffer<'de> + ?Sized> AsyncReadBuffer<'de> for T {
+    #[inline]
+    async fn read_n<const N: usize>(&mut self) -> Result<[u8; N], UcPackError> {
+        ReadBuffer::read_n(self)
+    }
+
+    #[inline]
+    async fn read_u8(&mut self) -> Result<u8, UcPackError> {
+        ReadBuffer::read_u8(self)
+    }
+
+    #[inline]
+    async fn read_slice(&mut self, out: &mut [u8]) -> Result<(), UcPackError> {
+        ReadBuffer::read_slice(self, out)
+    }
+}
+
+/// An [AsyncWriteBuffer] writing into an [embedded_io_async::Write] implementor,
+/// such as an Embassy UART, see [EmbeddedWriteAdapter](crate::buffer::EmbeddedWriteAdapter).
+#[cfg(feature = "embedded-io-async")]
+pub struct EmbeddedAsyncWriteAdapter<W>(pub W);
+
+#[cfg(feature = "embedded-io-async")]
+impl<W: embedded_io_async::Write> AsyncWriteBuffer for EmbeddedAsyncWriteAdapter<W> {
+    async fn push_slice(&mut self, bf: &[u8]) -> Result<(), UcPackError> {
+        self.0
+            .write_all(bf)
+            .await
+            .map_err(|_| UcPackError::BufferFull { needed: bf.len() })
+    }
+}
+
+/// An [AsyncReadBuffer] reading from an [embedded_io_async::Read] implementor,
+/// such as an Embassy UART, see [EmbeddedReadAdapter](crate::buffer::EmbeddedReadAdapter)
+/// for how errors are reported.
+#[cfg(feature = "embedded-io-async")]
+pub struct EmbeddedAsyncReadAdapter<R>(pub R);
+
+#[cfg(feature = "embedded-io-async")]
+impl<R: embedded_io_async::Read> AsyncReadBuffer<'_> for EmbeddedAsyncReadAdapter<R> {
+    #[inline]
+    async fn read_n<const N: usize>(&mut self) -> Result<[u8; N], UcPackError> {
+        let mut a = [0; N];
+        self.read_slice(&mut a).await?;
+        Ok(a)
+    }
+
+    async fn read_slice(&mut self, out: &mut [u8]) -> Result<(), UcPackError> {
+        // the number of bytes actually read is lost
+        self.0.read_exact(out).await.map_err(|err| match err {
+            embedded_io_async::ReadExactError::UnexpectedEof => UcPackError::Eof { needed: 1 },
+            embedded_io_async::ReadExactError::Other(_) => UcPackError::InvalidData,
+        })
+    }
+}
+
+/// An [AsyncWriteBuffer] writing into a [tokio::io::AsyncWrite] implementor,
+/// such as a [TcpStream](https://docs.rs/tokio/latest/tokio/net/struct.TcpStream.html).
+///
+/// The writer isn't flushed, so buffered writers have to be flushed by hand.
+#[cfg(feature = "tokio")]
+pub struct TokioWriteAdapter<W>(pub W);
+
+#[cfg(feature = "tokio")]
+impl<W: tokio::io::AsyncWrite + Unpin> AsyncWriteBuffer for TokioWriteAdapter<W> {
+    async fn push_slice(&mut self, bf: &[u8]) -> Result<(), UcPackError> {
+        tokio::io::AsyncWriteExt::write_all(&mut self.0, bf).await?;
+        Ok(())
+    }
+}
+
+/// An [AsyncReadBuffer] reading from a [tokio::io::AsyncRead] implementor,
+/// such as a [TcpStream](https://docs.rs/tokio/latest/tokio/net/struct.TcpStream.html).
+#[cfg(feature = "tokio")]
+pub struct TokioReadAdapter<R>(pub R);
+
+#[cfg(feature = "tokio")]
+impl<R: tokio::io::AsyncRead + Unpin> AsyncReadBuffer<'_> for TokioReadAdapter<R> {
+    #[inline]
+    async fn read_n<const N: usize>(&mut self) -> Result<[u8; N], UcPackError> {
+        let mut a = [0; N];
+        self.read_slice(&mut a).await?;
+        Ok(a)
+    }
+
+    async fn read_slice(&mut self, out: &mut [u8]) -> Result<(), UcPackError> {
+        tokio::io::AsyncReadExt::read_exact(&mut self.0, out).await?;
+        Ok(())
+    }
+}
+
+/// Serializes values into an [AsyncWriteBuffer], see [Serializer].
+pub struct AsyncSerializer<B: AsyncWriteBuffer> {
+    buffer: B,
+    tagged: bool,
+    wide_variants: bool,
+}
+
+impl<B: AsyncWriteBuffer> AsyncSerializer<B> {
+    pub fn new(buffer: B) -> Self {
+        Self {
+            buffer,
+            tagged: false,
+            wide_variants: false,
+        }
+    }
+
+    /// Enables or disables the tagged flavor of the format, where every value
+    /// is preceded by its [type tag](crate::tag).
+    pub fn with_tagged(self, tagged: bool) -> Self {
+        Self { tagged, ..self }
+    }
+
+    /// Encodes enum variant indices as little-endian `u16`s instead of a single byte.
+    pub fn with_wide_variants(self, wide_variants: bool) -> Self {
+        Self {
+            wide_variants,
+            ..self
+        }
+    }
+
+    /// Serializes `value`, then pushes it into the buffer, returning its size.
+    ///
+    /// Nothing is pushed if serialization fails. Values bigger than 255 bytes
+    /// are rejected with [UcPackError::TooLong].
+    pub async fn serialize(&mut self, value: &impl Serialize) -> Result<usize, UcPackError> {
+        let mut scratch = [0u8; SCRATCH_SIZE];
+        let mut cursor = SliceCursor::from_slice(&mut scratch[..]);
+
+        let mut serializer = Serializer::new(&mut cursor)
+            .with_tagged(self.tagged)
+            .with_wide_variants(self.wide_variants);
+        value.serialize(&mut serializer).map_err(|err| match err {
+            UcPackError::BufferFull { .. } => UcPackError::TooLong,
+            err => err,
+        })?;
+
+        let len = cursor.index();
+        self.buffer.push_slice(&scratch[..len]).await?;
+        Ok(len)
+    }
+
+    /// Consumes the serializer, returning the underlying buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+/// Deserializes values from an [AsyncReadBuffer], see [Deserializer].
+///
+/// As the ucpack format isn't self-delimiting, the size of every value
+/// (such as the length of the frame's payload) must be known beforehand.
+pub struct AsyncDeserializer<B> {
+    buffer: B,
+    tagged: bool,
+    wide_variants: bool,
+}
+
+impl<B> AsyncDeserializer<B> {
+    pub fn new(buffer: B) -> Self {
+        Self {
+            buffer,
+            tagged: false,
+            wide_variants: false,
+        }
+    }
+
+    /// Enables or disables the tagged flavor of the format, where every value
+    /// is preceded by its [type tag](crate::tag).
+    pub fn with_tagged(self, tagged: bool) -> Self {
+        Self { tagged, ..self }
+    }
+
+    /// Reads enum variant indices as little-endian `u16`s instead of a single byte.
+    pub fn with_wide_variants(self, wide_variants: bool) -> Self {
+        Self {
+            wide_variants,
+            ..self
+        }
+    }
+
+    /// Consumes the deserializer, returning the underlying buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+impl<'de, B: AsyncReadBuffer<'de>> AsyncDeserializer<B> {
+    /// Reads the next `len` bytes from the buffer, then deserializes them.
+    ///
+    /// Lengths bigger than 255 bytes are rejected with [UcPackError::TooLong],
+    /// without reading anything.
+    pub async fn deserialize<T: DeserializeOwned>(&mut self, len: usize) -> Result<T, UcPackError> {
+        let mut scratch = [0u8; SCRATCH_SIZE];
+        let data = scratch.get_mut(..len).ok_or(UcPackError::TooLong)?;
+        self.buffer.read_slice(data).await?;
+
+        let mut deserializer = Deserializer::new(ScratchCursor::from_slice(data))
+            .with_tagged(self.tagged)
+            .with_wide_variants(self.wide_variants);
+        T::deserialize(&mut deserializer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::{
+        future::Future,
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+
+    use super::{AsyncDeserializer, AsyncSerializer};
+    use crate::{buffer::SliceCursor, UcPack, UcPackError};
+
+    /// Polls a future which never suspends to completion
+    fn ready<F: Future>(future: F) -> F::Output {
+        match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("the future suspended"),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut buffer = [0u8; 8];
+        let mut serializer = AsyncSerializer::new(SliceCursor::from_slice(&mut buffer[..]));
+        assert_eq!(ready(serializer.serialize(&(1u8, 0x0203u16))).unwrap(), 3);
+        assert_eq!(ready(serializer.serialize(&true)).unwrap(), 1);
+        assert_eq!(buffer[..4], [1, 3, 2, 1]);
+
+        let mut deserializer = AsyncDeserializer::new(SliceCursor::from_slice(&buffer[..4]));
+        let value: (u8, u16) = ready(deserializer.deserialize(3)).unwrap();
+        assert_eq!(value, (1, 0x0203));
+        assert!(ready(deserializer.deserialize::<bool>(1)).unwrap());
+        assert!(matches!(
+            ready(deserializer.deserialize::<bool>(1)),
+            Err(UcPackError::Eof { .. })
+        ));
+    }
+
+    #[test]
+    fn too_long() {
+        let mut buffer = [0u8; 512];
+        let mut serializer = AsyncSerializer::new(SliceCursor::from_slice(&mut buffer[..]));
+        assert!(matches!(
+            ready(serializer.serialize(&[[0u8; 32]; 8])),
+            Err(UcPackError::TooLong)
+        ));
+
+        let mut deserializer = AsyncDeserializer::new(SliceCursor::from_slice(&[0u8; 512][..]));
+        assert!(matches!(
+            ready(deserializer.deserialize::<u8>(256)),
+            Err(UcPackError::TooLong)
+        ));
+    }
+
+    #[test]
+    fn frames() {
+        let ucpack = UcPack::default();
+
+        let mut buffer = [0u8; 16];
+        let mut writer = SliceCursor::from_slice(&mut buffer[..]);
+        assert_eq!(
+            ready(ucpack.serialize_async(&(1u8, 2u16), &mut writer)).unwrap(),
+            7
+        );
+        assert_eq!(
+            ready(ucpack.serialize_async(&true, &mut writer)).unwrap(),
+            5
+        );
+
+        let mut frame = [0u8; 7];
+        ucpack.serialize_slice(&(1u8, 2u16), &mut frame).unwrap();
+        assert_eq!(buffer[..7], frame);
+
+        let mut reader = SliceCursor::from_slice(&buffer[..12]);
+        let value: (u8, u16) = ready(ucpack.deserialize_async(&mut reader)).unwrap();
+        assert_eq!(value, (1, 2));
+        assert!(ready(ucpack.deserialize_async::<bool>(&mut reader)).unwrap());
+        assert!(matches!(
+            ready(ucpack.deserialize_async::<bool>(&mut reader)),
+            Err(UcPackError::Eof { .. })
+        ));
+
+        // corrupted frames are still verified
+        buffer[2] ^= 0xFF;
+        let mut reader = SliceCursor::from_slice(&buffer[..7]);
+        assert!(matches!(
+            ready(ucpack.deserialize_async::<(u8, u16)>(&mut reader)),
+            Err(UcPackError::WrongCrc { .. })
+        ));
+    }
+
+    #[test]
+    fn extended_frames() {
+        use crate::{buffer::WriteBuffer, checksum::Crc16, encoding::Encoding};
+
+        let payload = [[0x4123u16; 32]; 12];
+        let small = (b'A', b'#', [0x4123u16; 4]);
+        let ucpack = UcPack::default()
+            .with_extended_length(true)
+            .with_crc_covers_header(true)
+            .with_checksum(Crc16::new());
+        let escaped = ucpack.with_encoding(Encoding::Escaped { escape: 0x7D });
+
+        let mut expected = [0u8; 1024];
+        let mut buffer = [0u8; 1024];
+
+        // the payload takes multiple chunks
+        let n = ucpack.serialize_slice(&payload, &mut expected).unwrap();
+        assert!(n > 2 * crate::ASYNC_CHUNK_SIZE);
+        let mut writer = SliceCursor::from_slice(&mut buffer[..]);
+        assert_eq!(
+            ready(ucpack.serialize_async(&payload, &mut writer)).unwrap(),
+            n
+        );
+        assert_eq!(buffer[..n], expected[..n]);
+
+        #[cfg(feature = "alloc")]
+        {
+            let mut reader = SliceCursor::from_slice(&buffer[..n]);
+            let value: [[u16; 32]; 12] = ready(ucpack.deserialize_async(&mut reader)).unwrap();
+            assert_eq!(value, payload);
+        }
+
+        let n = escaped.serialize_slice(&small, &mut expected).unwrap();
+        let mut writer = SliceCursor::from_slice(&mut buffer[..]);
+        assert_eq!(
+            ready(escaped.serialize_async(&small, &mut writer)).unwrap(),
+            n
+        );
+        assert_eq!(buffer[..n], expected[..n]);
+
+        // nothing is pushed when the payload doesn't fit in a frame
+        let mut writer = SliceCursor::from_slice(&mut buffer[..]);
+        assert!(matches!(
+            ready(UcPack::default().serialize_async(&payload, &mut writer)),
+            Err(UcPackError::TooLong)
+        ));
+        assert_eq!(writer.bytes_written(), 0);
+    }
+
+    #[cfg(feature = "embedded-io-async")]
+    #[test]
+    fn embedded_io_async() {
+        use super::{EmbeddedAsyncReadAdapter, EmbeddedAsyncWriteAdapter};
+
+        /// A serial line receiving a single byte at a time
+        struct Serial<'a>(&'a [u8]);
+
+        impl embedded_io_async::ErrorType for Serial<'_> {
+            type Error = core::convert::Infallible;
+        }
+
+        impl embedded_io_async::Read for Serial<'_> {
+            async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+                let Some((&byte, rest)) = self.0.split_first() else {
+                    return Ok(0);
+                };
+
+                buf[0] = byte;
+                self.0 = rest;
+                Ok(1)
+            }
+        }
+
+        let ucpack = UcPack::default();
+        let mut line = [0u8; 8];
+        let mut tx = EmbeddedAsyncWriteAdapter(&mut line[..]);
+        let n = ready(ucpack.serialize_async(&(1u8, 2u16), &mut tx)).unwrap();
+        assert!(matches!(
+            ready(ucpack.serialize_async(&(1u8, 2u16), &mut tx)),
+            Err(UcPackError::BufferFull { .. })
+        ));
+
+        let mut rx = EmbeddedAsyncReadAdapter(Serial(&line[..n]));
+        let value: (u8, u16) = ready(ucpack.deserialize_async(&mut rx)).unwrap();
+        assert_eq!(value, (1, 2));
+        assert!(matches!(
+            ready(ucpack.deserialize_async::<(u8, u16)>(&mut rx)),
+            Err(UcPackError::Eof { .. })
+        ));
+    }
+}
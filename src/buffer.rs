@@ -2,6 +2,8 @@ use core::ops::{Deref, DerefMut};
 
 use crate::UcPackError;
 
+pub mod dynamic;
+
 /// A writeable buffer. Implemented by cursor types.
 ///
 /// You have to provide a method to copy &[u8] within.
@@ -12,12 +14,34 @@ pub trait WriteBuffer {
     fn push_u8(&mut self, byte: u8) -> Result<(), UcPackError> {
         self.push_slice(&[byte])
     }
+
+    /// Number of bytes successfully written into the buffer so far.
+    fn bytes_written(&self) -> usize;
+
+    /// Maximum number of bytes the buffer can hold, including the ones already written,
+    /// or `None` if it's unbounded (the default).
+    #[inline]
+    fn capacity(&self) -> Option<usize> {
+        None
+    }
+
+    /// Number of bytes which can still be pushed before hitting [UcPackError::BufferFull],
+    /// `usize::MAX` if the buffer is unbounded.
+    #[inline]
+    fn remaining_capacity(&self) -> usize {
+        self.capacity()
+            .map_or(usize::MAX, |cap| cap.saturating_sub(self.bytes_written()))
+    }
 }
 
 /// A readable buffer. Implemented by cursor types.
 ///
 /// You have to provide a method to copy-read N bytes from the buffer.
-pub trait ReadBuffer {
+///
+/// Buffers reading from data which lives for `'de` can also lend it out
+/// through [ReadBuffer::read_borrowed], allowing zero-copy deserialization
+/// of borrowed types such as `&'de [u8]`.
+pub trait ReadBuffer<'de> {
     // reads N bytes from the buffer, advancing its internal state, returning a
     // byte array of N bytes
     fn read_n<const N: usize>(&mut self) -> Result<[u8; N], UcPackError>;
@@ -26,13 +50,61 @@ pub trait ReadBuffer {
     fn read_u8(&mut self) -> Result<u8, UcPackError> {
         self.read_n().map(|[a]| a)
     }
+
+    /// Fills `out` with the next `out.len()` bytes of the buffer, advancing its internal state.
+    #[inline]
+    fn read_slice(&mut self, out: &mut [u8]) -> Result<(), UcPackError> {
+        for byte in out {
+            *byte = self.read_u8()?;
+        }
+
+        Ok(())
+    }
+
+    /// Discards the next `n` bytes of the buffer.
+    #[inline]
+    fn skip_n(&mut self, n: usize) -> Result<(), UcPackError> {
+        for _ in 0..n {
+            self.read_u8()?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads `len` bytes from the buffer, advancing its internal state, returning
+    /// a slice borrowed from the underlying data.
+    ///
+    /// Not supported by default, as buffers not backed by `'de` data can't lend it out.
+    /// Buffers returning [UcPackError::NoSupport] must leave their state untouched,
+    /// as the data is then read through [ReadBuffer::read_slice] instead.
+    #[inline]
+    fn read_borrowed(&mut self, _len: usize) -> Result<&'de [u8], UcPackError> {
+        Err(UcPackError::NoSupport("borrowed data"))
+    }
+
+    /// Returns the next byte without consuming it, allowing to branch on a leading
+    /// discriminator before actually reading the value.
+    ///
+    /// Not supported by default, as not every buffer can look ahead.
+    #[inline]
+    fn peek_u8(&self) -> Result<u8, UcPackError> {
+        Err(UcPackError::NoSupport("peeking"))
+    }
+
+    /// Number of bytes which are still left to be read,
+    /// `usize::MAX` if the buffer can't tell (the default).
+    #[inline]
+    fn remaining_bytes(&self) -> usize {
+        usize::MAX
+    }
 }
 
 /// A cursor over a byte slice.
 ///
-/// It implements [ReadBuffer] by default thanks to this bound:
+/// It implements [ReadBuffer] when reading from a borrowed slice,
+/// lending out data with the slice's lifetime:
 /// ```rs
-///     T: Deref<Target = [u8]>
+///     T = &'de [u8]
 /// ```
 ///
 /// However if the [WriteBuffer] implementation is required, the
@@ -40,7 +112,26 @@ pub trait ReadBuffer {
 /// ```rs
 ///     T: DerefMut<Target = [u8]> // implies Deref<Target = [u8]>
 /// ```
-pub(crate) struct SliceCursor<T>
+///
+/// Cursors can be used with the [Serializer](crate::ser::Serializer) and
+/// [Deserializer](crate::de::Deserializer) directly, for example to
+/// build custom framing around the payload:
+/// ```rust
+/// use serde::{Deserialize, Serialize};
+/// use ucpack::{de::Deserializer, ser::Serializer, SliceCursor};
+///
+/// let mut cursor = SliceCursor::from_slice(vec![0u8; 8]);
+/// (1u8, 2u16).serialize(&mut Serializer::new(&mut cursor)).unwrap();
+///
+/// let written = cursor.index();
+/// let buffer = cursor.into_inner();
+/// assert_eq!(buffer[..written], [1, 2, 0]);
+///
+/// let mut cursor = SliceCursor::from_slice(&buffer[..written]);
+/// let value = <(u8, u16)>::deserialize(&mut Deserializer::new(&mut cursor)).unwrap();
+/// assert_eq!(value, (1, 2));
+/// ```
+pub struct SliceCursor<T>
 where
     T: Deref<Target = [u8]>,
 {
@@ -63,25 +154,181 @@ impl<T: Deref<Target = [u8]>> SliceCursor<T> {
     pub fn inner(&self) -> &[u8] {
         &self.buffer
     }
+
+    /// Consumes the cursor, returning the wrapped buffer.
+    pub fn into_inner(self) -> T {
+        self.buffer
+    }
+
+    /// Position of the cursor within the buffer, same as [SliceCursor::index].
+    pub fn position(&self) -> usize {
+        self.index
+    }
+
+    /// Moves the cursor to `pos`, which can be anywhere from the
+    /// beginning up to the end (included) of the buffer.
+    ///
+    /// Returns [UcPackError::Eof] if `pos` is past the end of the buffer.
+    pub fn set_position(&mut self, pos: usize) -> Result<(), UcPackError> {
+        if pos > self.buffer.len() {
+            return Err(UcPackError::Eof {
+                needed: pos - self.buffer.len(),
+            });
+        }
+
+        self.index = pos;
+        Ok(())
+    }
+
+    /// Moves the cursor back to the beginning of the buffer, so that it can be reused
+    /// to serialize another payload, overwriting the previous one.
+    pub fn reset(&mut self) {
+        self.index = 0;
+    }
+
+    /// Moves the cursor to `pos` for writing, which can be anywhere from the
+    /// beginning up to the end (included) of the buffer.
+    ///
+    /// Returns [UcPackError::BufferFull] if `pos` is past the end of the buffer.
+    pub fn seek(&mut self, pos: usize) -> Result<(), UcPackError> {
+        self.set_position(pos).map_err(|_| UcPackError::BufferFull {
+            needed: pos - self.buffer.len(),
+        })
+    }
+
+    /// Saves the current position, so that it can be [restored](SliceCursor::restore)
+    /// if speculatively parsing the data that follows fails:
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use ucpack::{de::Deserializer, SliceCursor};
+    ///
+    /// let mut cursor = SliceCursor::from_slice(&[1, 2, 0][..]);
+    ///
+    /// let checkpoint = cursor.checkpoint();
+    /// let v1 = <(u8, bool)>::deserialize(&mut Deserializer::new(&mut cursor));
+    /// assert!(v1.is_err());
+    ///
+    /// cursor.restore(checkpoint).unwrap();
+    /// let v2 = <(u8, u16)>::deserialize(&mut Deserializer::new(&mut cursor));
+    /// assert_eq!(v2.unwrap(), (1, 2));
+    /// ```
+    pub fn checkpoint(&self) -> usize {
+        self.index
+    }
+
+    /// Moves the cursor back (or forward) to a position saved by [SliceCursor::checkpoint].
+    ///
+    /// Returns [UcPackError::Eof] if `checkpoint` is past the end of the buffer,
+    /// just like [SliceCursor::set_position].
+    pub fn restore(&mut self, checkpoint: usize) -> Result<(), UcPackError> {
+        self.set_position(checkpoint)
+    }
+
+    /// Returns the next `N` bytes without advancing the cursor.
+    pub fn peek_n<const N: usize>(&self) -> Result<[u8; N], UcPackError> {
+        self.buffer
+            .get(self.index..)
+            .and_then(|rest| rest.get(..N))
+            .map(|a| a.try_into().unwrap())
+            .ok_or_else(|| UcPackError::Eof {
+                needed: N - self.remaining(),
+            })
+    }
+
+    /// Number of bytes after the cursor: the ones yet to be read when reading,
+    /// or the remaining capacity when writing.
+    pub fn remaining(&self) -> usize {
+        self.buffer.len().saturating_sub(self.index)
+    }
 }
 
-impl<'a, T> ReadBuffer for SliceCursor<T>
-where
-    T: Deref<Target = [u8]>,
-{
+impl SliceCursor<&[u8]> {
+    /// Consumes the cursor, splitting its buffer into two non-overlapping cursors:
+    /// one over the bytes `0..pos` and one over the bytes `pos..`.
+    ///
+    /// Both cursors start from their beginning, regardless of the position of this one,
+    /// which allows a fixed size header and the payload following it to be deserialized
+    /// independently.
+    ///
+    /// Returns [UcPackError::Eof] if `pos` is past the end of the buffer.
+    pub fn split_at(self, pos: usize) -> Result<(Self, Self), UcPackError> {
+        let (prefix, suffix) =
+            self.buffer
+                .split_at_checked(pos)
+                .ok_or_else(|| UcPackError::Eof {
+                    needed: pos - self.buffer.len(),
+                })?;
+
+        Ok((Self::from_slice(prefix), Self::from_slice(suffix)))
+    }
+}
+
+/// Wraps `buffer` in a [SliceCursor] writing from its beginning.
+///
+/// Unlike [SliceCursor::from_slice] it accepts arrays as well, which makes
+/// serializing into a stack buffer a one-liner:
+/// ```rust
+/// use serde::Serialize;
+/// use ucpack::{buffer::cursor, ser::Serializer};
+///
+/// let mut buffer = [0u8; 8];
+/// let mut cursor = cursor(&mut buffer);
+/// (1u8, 2u16).serialize(&mut Serializer::new(&mut cursor)).unwrap();
+/// assert_eq!(cursor.index(), 3);
+/// ```
+pub fn cursor(buffer: &mut [u8]) -> SliceCursor<&mut [u8]> {
+    SliceCursor::from_slice(buffer)
+}
+
+impl<'de> ReadBuffer<'de> for SliceCursor<&'de [u8]> {
     #[inline]
     fn read_n<const N: usize>(&mut self) -> Result<[u8; N], UcPackError> {
-        let a = self
-            .buffer
-            .get(self.index..(self.index + N))
-            .ok_or(UcPackError::Eof)?
-            .try_into()
-            .unwrap();
+        let a = self.read_borrowed(N)?.try_into().unwrap();
+        Ok(a)
+    }
+
+    #[inline]
+    fn read_slice(&mut self, out: &mut [u8]) -> Result<(), UcPackError> {
+        out.copy_from_slice(self.read_borrowed(out.len())?);
+        Ok(())
+    }
+
+    #[inline]
+    fn skip_n(&mut self, n: usize) -> Result<(), UcPackError> {
+        if n > self.remaining() {
+            return Err(UcPackError::Eof {
+                needed: n - self.remaining(),
+            });
+        }
 
-        self.index += N;
+        self.index += n;
+        Ok(())
+    }
+
+    #[inline]
+    fn read_borrowed(&mut self, len: usize) -> Result<&'de [u8], UcPackError> {
+        let buffer: &'de [u8] = self.buffer;
+        let a = buffer
+            .get(self.index..)
+            .and_then(|rest| rest.get(..len))
+            .ok_or_else(|| UcPackError::Eof {
+                needed: len - self.remaining(),
+            })?;
+
+        self.index += len;
 
         Ok(a)
     }
+
+    #[inline]
+    fn peek_u8(&self) -> Result<u8, UcPackError> {
+        self.peek_n().map(|[a]| a)
+    }
+
+    #[inline]
+    fn remaining_bytes(&self) -> usize {
+        self.remaining()
+    }
 }
 
 impl<T> WriteBuffer for SliceCursor<T>
@@ -91,7 +338,9 @@ where
     fn push_slice(&mut self, data: &[u8]) -> Result<(), UcPackError> {
         let buffer = &mut self.buffer[self.index..];
         if data.len() > buffer.len() {
-            return Err(UcPackError::BufferFull);
+            return Err(UcPackError::BufferFull {
+                needed: data.len() - buffer.len(),
+            });
         }
 
         buffer[..data.len()].copy_from_slice(data); // copy from data
@@ -99,17 +348,531 @@ where
         self.index += data.len();
         Ok(())
     }
+
+    #[inline]
+    fn bytes_written(&self) -> usize {
+        self.index
+    }
+
+    #[inline]
+    fn capacity(&self) -> Option<usize> {
+        Some(self.buffer.len())
+    }
+}
+
+/// A cursor over a temporary slice (such as a scratch buffer holding
+/// a decoded payload), which can't lend its data out for `'de`.
+pub(crate) struct ScratchCursor<'a>(SliceCursor<&'a [u8]>);
+
+impl<'a> ScratchCursor<'a> {
+    pub fn from_slice(bf: &'a [u8]) -> Self {
+        Self(SliceCursor::from_slice(bf))
+    }
+}
+
+impl<'de> ReadBuffer<'de> for ScratchCursor<'_> {
+    #[inline]
+    fn read_n<const N: usize>(&mut self) -> Result<[u8; N], UcPackError> {
+        self.0.read_n()
+    }
+
+    #[inline]
+    fn read_slice(&mut self, out: &mut [u8]) -> Result<(), UcPackError> {
+        self.0.read_slice(out)
+    }
+
+    #[inline]
+    fn skip_n(&mut self, n: usize) -> Result<(), UcPackError> {
+        self.0.skip_n(n)
+    }
+
+    #[inline]
+    fn peek_u8(&self) -> Result<u8, UcPackError> {
+        self.0.peek_u8()
+    }
+
+    #[inline]
+    fn remaining_bytes(&self) -> usize {
+        self.0.remaining()
+    }
+}
+
+/// A [WriteBuffer] which discards its input, only keeping
+/// track of how many bytes have been pushed into it.
+///
+/// Useful to compute the serialized size of a payload without
+/// actually writing it anywhere.
+#[derive(Default)]
+pub(crate) struct CountingBuffer {
+    count: usize,
+}
+
+impl CountingBuffer {
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl WriteBuffer for CountingBuffer {
+    #[inline]
+    fn push_slice(&mut self, bf: &[u8]) -> Result<(), UcPackError> {
+        self.count += bf.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn bytes_written(&self) -> usize {
+        self.count
+    }
+}
+
+/// A [WriteBuffer] keeping only the bytes pushed after the first `skip` ones,
+/// until `window` is full.
+///
+/// Lets output which has to be produced all at once, such as a serialized payload,
+/// be collected one chunk at a time by producing it again for every chunk.
+/// Pushing past the end of the window fails with [UcPackError::BufferFull].
+#[cfg(feature = "async")]
+pub(crate) struct WindowBuffer<'a> {
+    window: &'a mut [u8],
+    skip: usize,
+    pushed: usize,
+}
+
+#[cfg(feature = "async")]
+impl<'a> WindowBuffer<'a> {
+    pub fn new(window: &'a mut [u8], skip: usize) -> Self {
+        Self {
+            window,
+            skip,
+            pushed: 0,
+        }
+    }
+
+    /// Number of bytes held by the window
+    pub fn len(&self) -> usize {
+        self.pushed.saturating_sub(self.skip).min(self.window.len())
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() == self.window.len()
+    }
+}
+
+#[cfg(feature = "async")]
+impl WriteBuffer for WindowBuffer<'_> {
+    fn push_slice(&mut self, bf: &[u8]) -> Result<(), UcPackError> {
+        let start = self.pushed;
+        self.pushed += bf.len();
+
+        let skipped = self.skip.saturating_sub(start).min(bf.len());
+        let kept = &bf[skipped..];
+        if kept.is_empty() {
+            return Ok(());
+        }
+
+        let offset = (start + skipped) - self.skip;
+
+        let n = kept.len().min(self.window.len().saturating_sub(offset));
+        if n > 0 {
+            self.window[offset..offset + n].copy_from_slice(&kept[..n]);
+        }
+
+        match n < kept.len() {
+            true => Err(UcPackError::BufferFull {
+                needed: kept.len() - n,
+            }),
+            false => Ok(()),
+        }
+    }
+
+    #[inline]
+    fn bytes_written(&self) -> usize {
+        self.len()
+    }
+}
+
+/// A fixed size FIFO of `N` bytes, such as the receive buffer an UART ISR fills
+/// and the main loop drains.
+///
+/// Bytes are pushed at the tail through [WriteBuffer] and popped from the head
+/// through [ReadBuffer], wrapping around the end of the array.
+/// As both sides need `&mut` access, sharing it between an interrupt and the main loop
+/// requires a critical section (e.g. a `Mutex<RefCell<RingBuffer<N>>>`).
+pub struct RingBuffer<const N: usize> {
+    buffer: [u8; N],
+    head: usize,
+    tail: usize,
+    len: usize,
+}
+
+impl<const N: usize> RingBuffer<N> {
+    pub const fn new() -> Self {
+        Self {
+            buffer: [0; N],
+            head: 0,
+            tail: 0,
+            len: 0,
+        }
+    }
+
+    /// Number of bytes pushed and not popped yet.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Discards every byte in the buffer.
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.tail = 0;
+        self.len = 0;
+    }
+
+    /// Moves `index` forward by `n` (at most `N`) bytes, wrapping around the end
+    fn advance(index: usize, n: usize) -> usize {
+        match index + n {
+            index if index >= N => index - N,
+            index => index,
+        }
+    }
+}
+
+impl<const N: usize> Default for RingBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> WriteBuffer for RingBuffer<N> {
+    fn push_slice(&mut self, data: &[u8]) -> Result<(), UcPackError> {
+        let free = N - self.len;
+        if data.len() > free {
+            return Err(UcPackError::BufferFull {
+                needed: data.len() - free,
+            });
+        }
+
+        // up to the end of the array, then from its beginning
+        let (first, second) = data.split_at(data.len().min(N - self.tail));
+        self.buffer[self.tail..][..first.len()].copy_from_slice(first);
+        self.buffer[..second.len()].copy_from_slice(second);
+
+        self.tail = Self::advance(self.tail, data.len());
+        self.len += data.len();
+        Ok(())
+    }
+
+    /// The bytes currently held, popped ones are not accounted for
+    #[inline]
+    fn bytes_written(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn capacity(&self) -> Option<usize> {
+        Some(N)
+    }
 }
 
+impl<'de, const N: usize> ReadBuffer<'de> for RingBuffer<N> {
+    #[inline]
+    fn read_n<const M: usize>(&mut self) -> Result<[u8; M], UcPackError> {
+        let mut a = [0; M];
+        self.read_slice(&mut a)?;
+        Ok(a)
+    }
+
+    fn read_slice(&mut self, out: &mut [u8]) -> Result<(), UcPackError> {
+        if out.len() > self.len {
+            return Err(UcPackError::Eof {
+                needed: out.len() - self.len,
+            });
+        }
+
+        let (first, second) = out.split_at_mut(out.len().min(N - self.head));
+        first.copy_from_slice(&self.buffer[self.head..][..first.len()]);
+        second.copy_from_slice(&self.buffer[..second.len()]);
+
+        self.skip_n(out.len())
+    }
+
+    fn skip_n(&mut self, n: usize) -> Result<(), UcPackError> {
+        if n > self.len {
+            return Err(UcPackError::Eof {
+                needed: n - self.len,
+            });
+        }
+
+        self.head = Self::advance(self.head, n);
+        self.len -= n;
+        Ok(())
+    }
+
+    #[inline]
+    fn peek_u8(&self) -> Result<u8, UcPackError> {
+        match self.len {
+            0 => Err(UcPackError::Eof { needed: 1 }),
+            _ => Ok(self.buffer[self.head]),
+        }
+    }
+
+    #[inline]
+    fn remaining_bytes(&self) -> usize {
+        self.len
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl WriteBuffer for alloc::vec::Vec<u8> {
+    fn push_slice(&mut self, bf: &[u8]) -> Result<(), UcPackError> {
+        self.extend_from_slice(bf);
+        Ok(())
+    }
+
+    /// The length of the vector, including anything it held beforehand
+    #[inline]
+    fn bytes_written(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Reads from the cursor's position onwards, just like [std::io::Read] would.
 #[cfg(feature = "std")]
-impl WriteBuffer for Vec<u8> {
+impl<'de, T: AsRef<[u8]>> ReadBuffer<'de> for std::io::Cursor<T> {
+    #[inline]
+    fn read_n<const N: usize>(&mut self) -> Result<[u8; N], UcPackError> {
+        let mut a = [0; N];
+        self.read_slice(&mut a)?;
+        Ok(a)
+    }
+
+    fn read_slice(&mut self, out: &mut [u8]) -> Result<(), UcPackError> {
+        // a failed read_exact would consume the remaining data
+        if out.len() > self.remaining_bytes() {
+            return Err(UcPackError::Eof {
+                needed: out.len() - self.remaining_bytes(),
+            });
+        }
+
+        std::io::Read::read_exact(self, out)?;
+        Ok(())
+    }
+
+    fn skip_n(&mut self, n: usize) -> Result<(), UcPackError> {
+        if n > self.remaining_bytes() {
+            return Err(UcPackError::Eof {
+                needed: n - self.remaining_bytes(),
+            });
+        }
+
+        self.set_position(self.position() + n as u64);
+        Ok(())
+    }
+
+    #[inline]
+    fn peek_u8(&self) -> Result<u8, UcPackError> {
+        let position = usize::try_from(self.position()).unwrap_or(usize::MAX);
+        let data = self.get_ref().as_ref();
+        data.get(position)
+            .copied()
+            .ok_or(UcPackError::Eof { needed: 1 })
+    }
+
+    #[inline]
+    fn remaining_bytes(&self) -> usize {
+        let position = usize::try_from(self.position()).unwrap_or(usize::MAX);
+        self.get_ref().as_ref().len().saturating_sub(position)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> WriteBuffer for heapless::Vec<u8, N> {
     fn push_slice(&mut self, bf: &[u8]) -> Result<(), UcPackError> {
+        self.extend_from_slice(bf)
+            .map_err(|_| UcPackError::BufferFull {
+                needed: bf.len() - (N - self.len()),
+            })
+    }
+
+    #[inline]
+    fn push_u8(&mut self, byte: u8) -> Result<(), UcPackError> {
+        self.push(byte)
+            .map_err(|_| UcPackError::BufferFull { needed: 1 })
+    }
+
+    #[inline]
+    fn bytes_written(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn capacity(&self) -> Option<usize> {
+        Some(N)
+    }
+}
+
+#[cfg(feature = "tinyvec")]
+impl<const N: usize> WriteBuffer for tinyvec::ArrayVec<[u8; N]> {
+    fn push_slice(&mut self, bf: &[u8]) -> Result<(), UcPackError> {
+        let free = N - self.len();
+        if bf.len() > free {
+            return Err(UcPackError::BufferFull {
+                needed: bf.len() - free,
+            });
+        }
+
         self.extend_from_slice(bf);
         Ok(())
     }
+
+    #[inline]
+    fn push_u8(&mut self, byte: u8) -> Result<(), UcPackError> {
+        match self.try_push(byte) {
+            None => Ok(()),
+            Some(_) => Err(UcPackError::BufferFull { needed: 1 }),
+        }
+    }
+
+    #[inline]
+    fn bytes_written(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn capacity(&self) -> Option<usize> {
+        Some(N)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl WriteBuffer for bytes::BytesMut {
+    fn push_slice(&mut self, bf: &[u8]) -> Result<(), UcPackError> {
+        bytes::BufMut::put_slice(self, bf);
+        Ok(())
+    }
+
+    /// The length of the buffer, including anything it held beforehand
+    #[inline]
+    fn bytes_written(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Reads from the front of the buffer, advancing it.
+#[cfg(feature = "bytes")]
+impl<'de> ReadBuffer<'de> for bytes::Bytes {
+    #[inline]
+    fn read_n<const N: usize>(&mut self) -> Result<[u8; N], UcPackError> {
+        let mut a = [0; N];
+        self.read_slice(&mut a)?;
+        Ok(a)
+    }
+
+    fn read_slice(&mut self, out: &mut [u8]) -> Result<(), UcPackError> {
+        if out.len() > self.len() {
+            return Err(UcPackError::Eof {
+                needed: out.len() - self.len(),
+            });
+        }
+
+        bytes::Buf::copy_to_slice(self, out);
+        Ok(())
+    }
+
+    fn skip_n(&mut self, n: usize) -> Result<(), UcPackError> {
+        if n > self.len() {
+            return Err(UcPackError::Eof {
+                needed: n - self.len(),
+            });
+        }
+
+        bytes::Buf::advance(self, n);
+        Ok(())
+    }
+
+    #[inline]
+    fn peek_u8(&self) -> Result<u8, UcPackError> {
+        self.first().copied().ok_or(UcPackError::Eof { needed: 1 })
+    }
+
+    #[inline]
+    fn remaining_bytes(&self) -> usize {
+        self.len()
+    }
+}
+
+/// A [WriteBuffer] writing straight into an [embedded_io::Write] implementor,
+/// such as an UART peripheral, without any intermediate buffer.
+///
+/// Since write errors can't be told apart, they're all reported as [UcPackError::BufferFull].
+/// The second field counts the bytes written so far, see [WriteBuffer::bytes_written].
+#[cfg(feature = "embedded-io")]
+pub struct EmbeddedWriteAdapter<W>(pub W, pub usize);
+
+#[cfg(feature = "embedded-io")]
+impl<W: embedded_io::Write> EmbeddedWriteAdapter<W> {
+    /// Wraps `writer`, with nothing written yet.
+    pub fn new(writer: W) -> Self {
+        Self(writer, 0)
+    }
 }
 
-impl<T: WriteBuffer> WriteBuffer for &mut T {
+#[cfg(feature = "embedded-io")]
+impl<W: embedded_io::Write> WriteBuffer for EmbeddedWriteAdapter<W> {
+    fn push_slice(&mut self, bf: &[u8]) -> Result<(), UcPackError> {
+        self.0
+            .write_all(bf)
+            .map_err(|_| UcPackError::BufferFull { needed: bf.len() })?;
+
+        self.1 += bf.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn bytes_written(&self) -> usize {
+        self.1
+    }
+}
+
+/// A [ReadBuffer] reading straight from an [embedded_io::Read] implementor,
+/// such as an UART peripheral, blocking until enough data is received.
+///
+/// The stream ending early is reported as [UcPackError::Eof], needing a single byte,
+/// while errors of the reader itself (such as UART framing or overrun errors) are
+/// reported as [UcPackError::InvalidData]. As the length of the stream
+/// isn't known, [ReadBuffer::remaining_bytes] always returns `usize::MAX`.
+/// See [UcPack::deserialize_read](crate::UcPack::deserialize_read) to read whole frames.
+#[cfg(feature = "embedded-io")]
+pub struct EmbeddedReadAdapter<R>(pub R);
+
+#[cfg(feature = "embedded-io")]
+impl<'de, R: embedded_io::Read> ReadBuffer<'de> for EmbeddedReadAdapter<R> {
+    #[inline]
+    fn read_n<const N: usize>(&mut self) -> Result<[u8; N], UcPackError> {
+        let mut a = [0; N];
+        self.read_slice(&mut a)?;
+        Ok(a)
+    }
+
+    fn read_slice(&mut self, out: &mut [u8]) -> Result<(), UcPackError> {
+        // the number of bytes actually read is lost
+        self.0.read_exact(out).map_err(|err| match err {
+            embedded_io::ReadExactError::UnexpectedEof => UcPackError::Eof { needed: 1 },
+            embedded_io::ReadExactError::Other(_) => UcPackError::InvalidData,
+        })
+    }
+
+    #[inline]
+    fn remaining_bytes(&self) -> usize {
+        usize::MAX
+    }
+}
+
+impl<T: WriteBuffer + ?Sized> WriteBuffer for &mut T {
     #[inline]
     fn push_slice(&mut self, bf: &[u8]) -> Result<(), UcPackError> {
         (**self).push_slice(bf)
@@ -119,9 +882,19 @@ impl<T: WriteBuffer> WriteBuffer for &mut T {
     fn push_u8(&mut self, byte: u8) -> Result<(), UcPackError> {
         (**self).push_u8(byte)
     }
+
+    #[inline]
+    fn bytes_written(&self) -> usize {
+        (**self).bytes_written()
+    }
+
+    #[inline]
+    fn capacity(&self) -> Option<usize> {
+        (**self).capacity()
+    }
 }
 
-impl<T: ReadBuffer> ReadBuffer for &mut T {
+impl<'de, T: ReadBuffer<'de> + ?Sized> ReadBuffer<'de> for &mut T {
     #[inline]
     fn read_u8(&mut self) -> Result<u8, UcPackError> {
         (**self).read_u8()
@@ -131,11 +904,37 @@ impl<T: ReadBuffer> ReadBuffer for &mut T {
     fn read_n<const N: usize>(&mut self) -> Result<[u8; N], UcPackError> {
         (**self).read_n()
     }
+
+    #[inline]
+    fn read_slice(&mut self, out: &mut [u8]) -> Result<(), UcPackError> {
+        (**self).read_slice(out)
+    }
+
+    #[inline]
+    fn skip_n(&mut self, n: usize) -> Result<(), UcPackError> {
+        (**self).skip_n(n)
+    }
+
+    #[inline]
+    fn read_borrowed(&mut self, len: usize) -> Result<&'de [u8], UcPackError> {
+        (**self).read_borrowed(len)
+    }
+
+    #[inline]
+    fn peek_u8(&self) -> Result<u8, UcPackError> {
+        (**self).peek_u8()
+    }
+
+    #[inline]
+    fn remaining_bytes(&self) -> usize {
+        (**self).remaining_bytes()
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use super::{SliceCursor, WriteBuffer};
+    use super::{CountingBuffer, ReadBuffer, RingBuffer, SliceCursor, WriteBuffer};
+    use crate::UcPackError;
 
     #[test]
     fn full_err() {
@@ -144,5 +943,368 @@ mod test {
 
         cursor.push_slice(&[1, 2, 3, 4, 5]).unwrap();
         cursor.push_u8(1).unwrap_err();
+
+        let mut cursor = SliceCursor::from_slice(&mut a[..]);
+        cursor.push_slice(&[1, 2, 3]).unwrap();
+        assert_eq!(cursor.capacity(), Some(5));
+        assert_eq!(cursor.remaining_capacity(), 2);
+        assert_eq!(cursor.remaining(), 2);
+        assert_eq!(cursor.bytes_written(), 3);
+        assert!(matches!(
+            cursor.push_slice(&[4, 5, 6, 7]),
+            Err(UcPackError::BufferFull { needed: 2 })
+        ));
+        assert_eq!(cursor.bytes_written(), 3);
+
+        cursor.push_slice(&[4, 5]).unwrap();
+        assert_eq!(cursor.remaining_capacity(), 0);
+    }
+
+    #[test]
+    fn remaining() {
+        let data = [1, 2, 3, 4];
+        let mut cursor = SliceCursor::from_slice(&data[..]);
+        assert_eq!(cursor.remaining(), 4);
+
+        cursor.read_n::<3>().unwrap();
+        assert_eq!(cursor.remaining(), 1);
+
+        cursor.read_u8().unwrap();
+        assert_eq!(cursor.remaining(), 0);
+        cursor.read_u8().unwrap_err();
+        assert_eq!(cursor.remaining(), 0);
+    }
+
+    #[test]
+    fn peek() {
+        let data = [1, 2, 3];
+        let mut cursor = SliceCursor::from_slice(&data[..]);
+
+        assert_eq!(cursor.peek_n::<2>().unwrap(), [1, 2]);
+        assert_eq!(cursor.peek_n::<2>().unwrap(), [1, 2]);
+        assert_eq!(cursor.index(), 0);
+
+        cursor.read_u8().unwrap();
+        assert_eq!(cursor.peek_n::<2>().unwrap(), [2, 3]);
+        assert!(matches!(cursor.peek_n::<3>(), Err(UcPackError::Eof { .. })));
+        assert_eq!(cursor.peek_n::<0>().unwrap(), []);
+        assert_eq!(cursor.read_n::<2>().unwrap(), [2, 3]);
+    }
+
+    #[test]
+    fn peek_u8() {
+        let data = [1, 2];
+        let mut cursor = SliceCursor::from_slice(&data[..]);
+
+        assert_eq!(cursor.peek_u8().unwrap(), 1);
+        assert_eq!(cursor.peek_u8().unwrap(), 1);
+        assert_eq!(cursor.read_u8().unwrap(), 1);
+        assert_eq!(cursor.peek_u8().unwrap(), 2);
+        assert_eq!(cursor.read_u8().unwrap(), 2);
+        assert!(matches!(cursor.peek_u8(), Err(UcPackError::Eof { .. })));
+
+        let mut ring = RingBuffer::<2>::new();
+        ring.push_slice(&[1, 2]).unwrap();
+        ring.skip_n(1).unwrap();
+        ring.push_u8(3).unwrap();
+        assert_eq!(ring.peek_u8().unwrap(), 2);
+        assert_eq!(ring.read_n::<2>().unwrap(), [2, 3]);
+        assert!(matches!(ring.peek_u8(), Err(UcPackError::Eof { .. })));
+    }
+
+    #[test]
+    fn set_position() {
+        let data = [1, 2, 3];
+        let mut cursor = SliceCursor::from_slice(&data[..]);
+
+        cursor.read_n::<2>().unwrap();
+        assert_eq!(cursor.position(), 2);
+
+        cursor.set_position(0).unwrap();
+        assert_eq!(cursor.read_u8().unwrap(), 1);
+
+        cursor.set_position(3).unwrap();
+        cursor.read_u8().unwrap_err();
+        assert!(matches!(
+            cursor.set_position(4),
+            Err(UcPackError::Eof { .. })
+        ));
+        assert_eq!(cursor.position(), 3);
+    }
+
+    #[test]
+    fn checkpoint() {
+        let data = [1, 2, 3];
+        let mut cursor = SliceCursor::from_slice(&data[..]);
+
+        cursor.read_u8().unwrap();
+        let checkpoint = cursor.checkpoint();
+        assert_eq!(cursor.read_n::<2>().unwrap(), [2, 3]);
+        cursor.read_u8().unwrap_err();
+
+        cursor.restore(checkpoint).unwrap();
+        assert_eq!(cursor.read_n::<2>().unwrap(), [2, 3]);
+        assert!(matches!(cursor.restore(4), Err(UcPackError::Eof { .. })));
+        assert_eq!(cursor.index(), 3);
+    }
+
+    #[test]
+    fn split_at() {
+        let data = [1, 2, 3, 4, 5];
+        let cursor = SliceCursor::from_slice(&data[..]);
+
+        let (mut header, mut payload) = cursor.split_at(2).unwrap();
+        assert_eq!(payload.read_n::<3>().unwrap(), [3, 4, 5]);
+        assert_eq!(header.read_n::<2>().unwrap(), [1, 2]);
+        assert!(matches!(header.read_u8(), Err(UcPackError::Eof { .. })));
+
+        let cursor = SliceCursor::from_slice(&data[..]);
+        assert!(matches!(cursor.split_at(6), Err(UcPackError::Eof { .. })));
+
+        let cursor = SliceCursor::from_slice(&data[..]);
+        let (header, payload) = cursor.split_at(5).unwrap();
+        assert_eq!((header.remaining(), payload.remaining()), (5, 0));
+    }
+
+    #[test]
+    fn skip_n() {
+        let data = [1, 2, 3, 4];
+        let mut cursor = SliceCursor::from_slice(&data[..]);
+
+        cursor.skip_n(2).unwrap();
+        assert_eq!(cursor.read_u8().unwrap(), 3);
+
+        assert!(matches!(cursor.skip_n(2), Err(UcPackError::Eof { .. })));
+        assert_eq!(cursor.index(), 3);
+        cursor.skip_n(1).unwrap();
+        cursor.skip_n(0).unwrap();
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec() {
+        let mut vec = alloc::vec::Vec::new();
+        vec.push_slice(&[1, 2]).unwrap();
+        vec.push_u8(3).unwrap();
+        assert_eq!(vec.bytes_written(), 3);
+        assert_eq!(vec.remaining_capacity(), usize::MAX);
+
+        let frame = crate::UcPack::default()
+            .serialize_vec(&(1u8, 2u16))
+            .unwrap();
+        assert_eq!(frame[2..5], [1, 2, 0]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn io_cursor() {
+        use std::io::{Cursor, Read};
+
+        let mut cursor = Cursor::new(vec![1, 2, 3, 4]);
+        assert_eq!(cursor.read_u8().unwrap(), 1);
+
+        // mixing with std::io::Read
+        let mut byte = [0];
+        cursor.read_exact(&mut byte).unwrap();
+        assert_eq!(byte, [2]);
+
+        assert_eq!(cursor.peek_u8().unwrap(), 3);
+        assert_eq!(cursor.remaining_bytes(), 2);
+        assert!(matches!(cursor.read_n::<3>(), Err(UcPackError::Eof { .. })));
+        assert!(matches!(cursor.skip_n(3), Err(UcPackError::Eof { .. })));
+        assert_eq!(cursor.read_n::<2>().unwrap(), [3, 4]);
+        assert!(matches!(cursor.read_u8(), Err(UcPackError::Eof { .. })));
+        assert!(matches!(cursor.peek_u8(), Err(UcPackError::Eof { .. })));
+    }
+
+    #[cfg(feature = "embedded-io")]
+    #[test]
+    fn embedded_io() {
+        use super::EmbeddedWriteAdapter;
+
+        let mut a = [0u8; 4];
+        let mut adapter = EmbeddedWriteAdapter::new(&mut a[..]);
+        adapter.push_slice(&[1, 2]).unwrap();
+        adapter.push_u8(3).unwrap();
+        assert_eq!(adapter.bytes_written(), 3);
+
+        assert!(matches!(
+            adapter.push_slice(&[4, 5]),
+            Err(UcPackError::BufferFull { needed: 2 })
+        ));
+        assert_eq!(a[..3], [1, 2, 3]);
+    }
+
+    #[cfg(feature = "embedded-io")]
+    #[test]
+    fn embedded_io_read() {
+        use super::EmbeddedReadAdapter;
+
+        let mut adapter = EmbeddedReadAdapter(&[1, 2, 3][..]);
+        assert_eq!(adapter.read_u8().unwrap(), 1);
+        assert_eq!(adapter.read_n().unwrap(), [2, 3]);
+        assert!(matches!(adapter.read_u8(), Err(UcPackError::Eof { .. })));
+    }
+
+    #[cfg(feature = "tinyvec")]
+    #[test]
+    fn tinyvec() {
+        let mut vec = tinyvec::ArrayVec::<[u8; 4]>::new();
+        vec.push_slice(&[1, 2]).unwrap();
+        vec.push_u8(3).unwrap();
+        assert_eq!(vec.bytes_written(), 3);
+        assert_eq!(vec.remaining_capacity(), 1);
+
+        assert!(matches!(
+            vec.push_slice(&[4, 5, 6]),
+            Err(UcPackError::BufferFull { needed: 2 })
+        ));
+        vec.push_u8(4).unwrap();
+        assert!(matches!(
+            vec.push_u8(5),
+            Err(UcPackError::BufferFull { needed: 1 })
+        ));
+        assert_eq!(vec.as_slice(), [1, 2, 3, 4]);
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn heapless() {
+        let mut vec = heapless::Vec::<u8, 4>::new();
+        vec.push_slice(&[1, 2]).unwrap();
+        vec.push_u8(3).unwrap();
+        assert_eq!(vec.bytes_written(), 3);
+        assert_eq!(vec.remaining_capacity(), 1);
+
+        assert!(matches!(
+            vec.push_slice(&[4, 5, 6]),
+            Err(UcPackError::BufferFull { needed: 2 })
+        ));
+        vec.push_u8(4).unwrap();
+        assert!(matches!(
+            vec.push_u8(5),
+            Err(UcPackError::BufferFull { needed: 1 })
+        ));
+        assert_eq!(vec, [1, 2, 3, 4]);
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn bytes() {
+        use serde::{Deserialize, Serialize};
+
+        use crate::{de::Deserializer, ser::Serializer};
+
+        let mut buffer = bytes::BytesMut::new();
+        (1u8, 2u16, true)
+            .serialize(&mut Serializer::new(&mut buffer))
+            .unwrap();
+        assert_eq!(buffer.bytes_written(), 4);
+
+        let mut bytes = buffer.freeze();
+        assert_eq!(bytes.peek_u8().unwrap(), 1);
+        let value = <(u8, u16)>::deserialize(&mut Deserializer::new(&mut bytes)).unwrap();
+        assert_eq!(value, (1, 2));
+        assert_eq!(bytes.remaining_bytes(), 1);
+
+        assert!(matches!(bytes.read_n::<2>(), Err(UcPackError::Eof { .. })));
+        assert_eq!(bytes.read_u8().unwrap(), 1);
+        assert!(matches!(bytes.skip_n(1), Err(UcPackError::Eof { .. })));
+    }
+
+    #[test]
+    fn counting() {
+        let mut counter = CountingBuffer::default();
+
+        counter.push_slice(&[1, 2, 3]).unwrap();
+        counter.push_u8(4).unwrap();
+        assert_eq!(counter.count(), 4);
+        assert_eq!(counter.bytes_written(), 4);
+        assert_eq!(counter.capacity(), None);
+        assert_eq!(counter.remaining_capacity(), usize::MAX);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn window() {
+        use super::WindowBuffer;
+
+        let mut chunk = [0u8; 3];
+        let mut window = WindowBuffer::new(&mut chunk, 2);
+
+        window.push_slice(&[1]).unwrap();
+        window.push_slice(&[2, 3]).unwrap();
+        assert_eq!(window.len(), 1);
+        window.push_u8(4).unwrap();
+        assert!(matches!(
+            window.push_slice(&[5, 6, 7]),
+            Err(UcPackError::BufferFull { needed: 2 })
+        ));
+        assert!(window.is_full());
+        assert_eq!(chunk, [3, 4, 5]);
+    }
+
+    #[test]
+    fn ring_wrap_around() {
+        let mut ring = RingBuffer::<300>::new();
+        ring.push_slice(&[0; 100]).unwrap();
+        ring.skip_n(100).unwrap();
+        assert!(ring.is_empty());
+
+        // a whole packet crossing the end of the array
+        let mut packet = [0u8; 255];
+        for (byte, value) in packet.iter_mut().zip(0..) {
+            *byte = value;
+        }
+        ring.push_slice(&packet).unwrap();
+        assert_eq!(ring.len(), 255);
+
+        let mut out = [0u8; 255];
+        ring.read_slice(&mut out).unwrap();
+        assert_eq!(out, packet);
+        assert_eq!(ring.remaining_bytes(), 0);
+        assert!(matches!(ring.read_u8(), Err(UcPackError::Eof { .. })));
+
+        // a single value split between the end and the beginning
+        let mut ring = RingBuffer::<4>::new();
+        ring.push_slice(&[1, 2, 3]).unwrap();
+        assert_eq!(ring.read_n::<3>().unwrap(), [1, 2, 3]);
+        ring.push_slice(&[4, 5]).unwrap();
+        assert_eq!(ring.read_n::<2>().unwrap(), [4, 5]);
+    }
+
+    #[test]
+    fn ring_full() {
+        let mut ring = RingBuffer::<4>::new();
+        ring.push_slice(&[1, 2, 3]).unwrap();
+        assert_eq!(ring.capacity(), Some(4));
+        assert!(matches!(
+            ring.push_slice(&[4, 5, 6]),
+            Err(UcPackError::BufferFull { needed: 2 })
+        ));
+
+        assert_eq!(ring.read_u8().unwrap(), 1);
+        assert_eq!(ring.remaining_capacity(), 2);
+        ring.push_slice(&[4, 5]).unwrap();
+        assert_eq!(ring.bytes_written(), 4);
+        assert_eq!(ring.read_n::<4>().unwrap(), [2, 3, 4, 5]);
+
+        ring.push_u8(6).unwrap();
+        ring.clear();
+        assert!(matches!(ring.read_u8(), Err(UcPackError::Eof { .. })));
+    }
+
+    #[test]
+    fn remaining_bytes_unknown() {
+        // a buffer only implementing the required methods
+        struct Zeroes;
+
+        impl ReadBuffer<'_> for Zeroes {
+            fn read_n<const N: usize>(&mut self) -> Result<[u8; N], UcPackError> {
+                Ok([0; N])
+            }
+        }
+
+        assert_eq!(Zeroes.remaining_bytes(), usize::MAX);
     }
 }
@@ -0,0 +1,105 @@
+//! Runtime-polymorphic buffers.
+//!
+//! [ReadBuffer::read_n] is generic over the number of bytes it reads, so `dyn ReadBuffer`
+//! can't exist: [DynReadBuffer] works around it by only reading through slices.
+//! [WriteBuffer](super::WriteBuffer) on the other hand is already dyn-compatible.
+
+use super::ReadBuffer;
+use crate::UcPackError;
+
+/// A dyn-compatible counterpart of [ReadBuffer], for when the buffer can only
+/// be known at runtime.
+///
+/// It's implemented by every [ReadBuffer], and `dyn DynReadBuffer` implements
+/// [ReadBuffer] in turn, so a `&mut dyn DynReadBuffer` can be handed to a
+/// [Deserializer](crate::de::Deserializer) just like any other buffer.
+///
+/// Its methods share their names with [ReadBuffer]'s, so it's best not to import both.
+/// ```rust
+/// use serde::Deserialize;
+/// use ucpack::{buffer::{dynamic::DynReadBuffer, SliceCursor}, de::Deserializer};
+///
+/// let data = [1, 2, 0];
+/// let mut cursor = SliceCursor::from_slice(&data[..]);
+/// let buffer: &mut dyn DynReadBuffer = &mut cursor;
+///
+/// let value = <(u8, u16)>::deserialize(&mut Deserializer::new(buffer)).unwrap();
+/// assert_eq!(value, (1, 2));
+/// ```
+pub trait DynReadBuffer<'de> {
+    /// See [ReadBuffer::read_slice].
+    fn read_slice(&mut self, out: &mut [u8]) -> Result<(), UcPackError>;
+
+    /// See [ReadBuffer::skip_n].
+    fn skip_n(&mut self, n: usize) -> Result<(), UcPackError>;
+
+    /// See [ReadBuffer::read_borrowed].
+    fn read_borrowed(&mut self, len: usize) -> Result<&'de [u8], UcPackError>;
+
+    /// See [ReadBuffer::peek_u8].
+    fn peek_u8(&self) -> Result<u8, UcPackError>;
+
+    /// See [ReadBuffer::remaining_bytes].
+    fn remaining_bytes(&self) -> usize;
+}
+
+impl<'de, T: ReadBuffer<'de>> DynReadBuffer<'de> for T {
+    #[inline]
+    fn read_slice(&mut self, out: &mut [u8]) -> Result<(), UcPackError> {
+        ReadBuffer::read_slice(self, out)
+    }
+
+    #[inline]
+    fn skip_n(&mut self, n: usize) -> Result<(), UcPackError> {
+        ReadBuffer::skip_n(self, n)
+    }
+
+    #[inline]
+    fn read_borrowed(&mut self, len: usize) -> Result<&'de [u8], UcPackError> {
+        ReadBuffer::read_borrowed(self, len)
+    }
+
+    #[inline]
+    fn peek_u8(&self) -> Result<u8, UcPackError> {
+        ReadBuffer::peek_u8(self)
+    }
+
+    #[inline]
+    fn remaining_bytes(&self) -> usize {
+        ReadBuffer::remaining_bytes(self)
+    }
+}
+
+impl<'de> ReadBuffer<'de> for dyn DynReadBuffer<'de> + '_ {
+    #[inline]
+    fn read_n<const N: usize>(&mut self) -> Result<[u8; N], UcPackError> {
+        let mut out = [0; N];
+        DynReadBuffer::read_slice(self, &mut out)?;
+        Ok(out)
+    }
+
+    #[inline]
+    fn read_slice(&mut self, out: &mut [u8]) -> Result<(), UcPackError> {
+        DynReadBuffer::read_slice(self, out)
+    }
+
+    #[inline]
+    fn skip_n(&mut self, n: usize) -> Result<(), UcPackError> {
+        DynReadBuffer::skip_n(self, n)
+    }
+
+    #[inline]
+    fn read_borrowed(&mut self, len: usize) -> Result<&'de [u8], UcPackError> {
+        DynReadBuffer::read_borrowed(self, len)
+    }
+
+    #[inline]
+    fn peek_u8(&self) -> Result<u8, UcPackError> {
+        DynReadBuffer::peek_u8(self)
+    }
+
+    #[inline]
+    fn remaining_bytes(&self) -> usize {
+        DynReadBuffer::remaining_bytes(self)
+    }
+}
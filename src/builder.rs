@@ -0,0 +1,121 @@
+use crate::{
+    checksum::{Checksum, Crc8},
+    encoding::Encoding,
+    UcPack,
+};
+
+/// Configures a [UcPack] one option at a time, starting from the default one.
+///
+/// Every option mirrors one of the `UcPack::with_*` methods.
+/// ```rust
+/// use ucpack::{checksum::Crc16, UcPack};
+///
+/// let ucpack = UcPack::builder()
+///     .start(b'B')
+///     .end(b'$')
+///     .strict(false)
+///     .crc(Crc16::new())
+///     .build();
+///
+/// let mut frame = [0u8; 8];
+/// let n = ucpack.serialize_slice(&(1u8, 2u16), &mut frame).unwrap();
+/// assert_eq!(frame[0], b'B');
+/// assert_eq!(frame[n - 3], b'$');
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct UcPackBuilder<C = Crc8> {
+    ucpack: UcPack<C>,
+}
+
+impl UcPackBuilder {
+    pub const fn new() -> Self {
+        Self {
+            ucpack: UcPack::new(b'A', b'#'),
+        }
+    }
+}
+
+impl Default for UcPackBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Checksum> UcPackBuilder<C> {
+    /// Sets the start index of every frame.
+    pub const fn start(mut self, start_index: u8) -> Self {
+        self.ucpack.start_index = start_index;
+        self
+    }
+
+    /// Sets the end index of every frame.
+    pub const fn end(mut self, end_index: u8) -> Self {
+        self.ucpack.end_index = end_index;
+        self
+    }
+
+    /// See [UcPack::with_strict].
+    pub const fn strict(self, strict: bool) -> Self {
+        Self {
+            ucpack: self.ucpack.with_strict(strict),
+        }
+    }
+
+    /// See [UcPack::with_checksum].
+    pub const fn crc<D: Checksum>(self, checksum: D) -> UcPackBuilder<D> {
+        UcPackBuilder {
+            ucpack: self.ucpack.with_checksum(checksum),
+        }
+    }
+
+    /// See [UcPack::with_extended_length].
+    pub const fn extended_length(self, extended_length: bool) -> Self {
+        Self {
+            ucpack: self.ucpack.with_extended_length(extended_length),
+        }
+    }
+
+    /// See [UcPack::with_tagged].
+    pub const fn tagged(self, tagged: bool) -> Self {
+        Self {
+            ucpack: self.ucpack.with_tagged(tagged),
+        }
+    }
+
+    /// See [UcPack::with_wide_variants].
+    pub const fn wide_variants(self, wide_variants: bool) -> Self {
+        Self {
+            ucpack: self.ucpack.with_wide_variants(wide_variants),
+        }
+    }
+
+    /// See [UcPack::with_encoding].
+    pub const fn encoding(self, encoding: Encoding) -> Self {
+        Self {
+            ucpack: self.ucpack.with_encoding(encoding),
+        }
+    }
+
+    /// See [UcPack::with_deny_trailing_bytes].
+    pub const fn deny_trailing_bytes(self, deny_trailing_bytes: bool) -> Self {
+        Self {
+            ucpack: self.ucpack.with_deny_trailing_bytes(deny_trailing_bytes),
+        }
+    }
+
+    /// See [UcPack::with_crc_covers_header].
+    pub const fn crc_covers_header(self, crc_covers_header: bool) -> Self {
+        Self {
+            ucpack: self.ucpack.with_crc_covers_header(crc_covers_header),
+        }
+    }
+
+    /// # Panics
+    /// If the escape byte of [Encoding::Escaped] is equal to the start or end index,
+    /// see [UcPack::with_encoding].
+    pub const fn build(self) -> UcPack<C> {
+        // the indices may have been changed after the encoding was set
+        let encoding = self.ucpack.encoding;
+        self.ucpack.with_encoding(encoding)
+    }
+}
@@ -0,0 +1,84 @@
+//! Faster (de)serialization of byte arrays, through `#[serde(with = "ucpack::byte_array")]`.
+//!
+//! serde treats a `[u8; N]` as a tuple of `N` bytes, read one at a time, and only
+//! up to `N = 32`. Arrays using this module have the same representation,
+//! but they work with any `N` and are read all at once in the untagged format
+//! (as long as they're at most 256 bytes long), borrowing from the buffer when possible.
+//! ```rust
+//! use ucpack::UcPack;
+//!
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Chunk {
+//!     offset: u16,
+//!     #[serde(with = "ucpack::byte_array")]
+//!     data: [u8; 64],
+//! }
+//!
+//! let ucpack = UcPack::default();
+//! let chunk = Chunk { offset: 128, data: [0xAA; 64] };
+//! let mut frame = [0u8; 70];
+//! ucpack.serialize_slice(&chunk, &mut frame).unwrap();
+//!
+//! let chunk: Chunk = ucpack.deserialize_slice(&frame).unwrap();
+//! assert_eq!(chunk.data, [0xAA; 64]);
+//! ```
+
+use core::fmt;
+
+use serde::{
+    de::{Error, SeqAccess, Visitor},
+    ser::SerializeTupleStruct,
+    Deserializer, Serializer,
+};
+
+/// Name of the tuple struct byte arrays are (de)serialized as,
+/// recognized by [crate::de::Deserializer]
+pub(crate) const NAME: &str = "$ucpack::byte_array";
+
+/// Longest array which is read all at once
+pub(crate) const MAX_BULK_LEN: usize = 256;
+
+pub fn serialize<S: Serializer, const N: usize>(
+    array: &[u8; N],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut tuple = serializer.serialize_tuple_struct(NAME, N)?;
+    for byte in array {
+        tuple.serialize_field(byte)?;
+    }
+
+    tuple.end()
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+    deserializer: D,
+) -> Result<[u8; N], D::Error> {
+    deserializer.deserialize_tuple_struct(NAME, N, ByteArrayVisitor)
+}
+
+struct ByteArrayVisitor<const N: usize>;
+
+impl<'de, const N: usize> Visitor<'de> for ByteArrayVisitor<N> {
+    type Value = [u8; N];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "an array of {N} bytes")
+    }
+
+    fn visit_bytes<E: Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+        bytes
+            .try_into()
+            .map_err(|_| E::invalid_length(bytes.len(), &self))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut array = [0u8; N];
+        for (i, byte) in array.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(i, &self))?;
+        }
+
+        Ok(array)
+    }
+}
@@ -0,0 +1,455 @@
+//! Checksums which can protect the payload of a frame.
+//!
+//! Every frame ends with the end index followed by a checksum of the payload.
+//! The default [Crc8] is a single byte, like in the other ucpack implementations,
+//! while [Crc16] trades one more byte per frame for much better error detection.
+//! Any other algorithm can be plugged in by implementing [Checksum]:
+//! ```rust
+//! use ucpack::{checksum::Checksum, UcPack};
+//!
+//! /// XOR of all the payload bytes
+//! #[derive(Clone, Copy, Default)]
+//! struct Xor(u8);
+//!
+//! impl Checksum for Xor {
+//!     type Output = [u8; 1];
+//!     const SIZE: usize = 1;
+//!
+//!     fn update(&mut self, data: &[u8]) {
+//!         self.0 = data.iter().fold(self.0, |xor, byte| xor ^ byte);
+//!     }
+//!
+//!     fn finalize(&self) -> [u8; 1] {
+//!         [self.0]
+//!     }
+//! }
+//!
+//! let ucpack = UcPack::default().with_checksum(Xor::default());
+//! let mut frame = [0u8; 6];
+//! ucpack.serialize_slice(&(1u8, 2u8), &mut frame).unwrap();
+//! assert_eq!(frame, [b'A', 2, 1, 2, b'#', 3]);
+//! ```
+
+use crate::{buffer::WriteBuffer, UcPackError};
+
+/// A checksum algorithm, computed over the payload of every frame.
+///
+/// The value held by [UcPack](crate::UcPack) is the initial state of the checksum,
+/// which is copied for every frame, fed with the whole payload and finalized.
+pub trait Checksum: Copy {
+    /// The checksum, as appended to frames.
+    type Output: AsRef<[u8]>;
+
+    /// Number of bytes of [Checksum::Output].
+    const SIZE: usize;
+
+    /// Feeds `data` to the checksum.
+    fn update(&mut self, data: &[u8]);
+
+    /// Returns the checksum of all the data fed so far.
+    fn finalize(&self) -> Self::Output;
+}
+
+/// A [WriteBuffer] computing the checksum of the data pushed into it on the fly,
+/// before forwarding it to the wrapped buffer.
+///
+/// This way the data never has to be read back, so the wrapped buffer may as well
+/// be a write-only sink.
+/// ```rust
+/// use serde::Serialize;
+/// use ucpack::{checksum::{CrcWriter, Crc8}, ser::Serializer, SliceCursor};
+///
+/// let mut buffer = [0u8; 3];
+/// let mut writer = CrcWriter::new(SliceCursor::from_slice(&mut buffer[..]), Crc8::new());
+/// (1u8, 2u16).serialize(&mut Serializer::new(&mut writer)).unwrap();
+///
+/// assert_eq!(writer.finalize(), [ucpack::crc8_slice(&[1, 2, 0])]);
+/// assert_eq!(writer.into_inner().index(), 3);
+/// assert_eq!(buffer, [1, 2, 0]);
+/// ```
+pub struct CrcWriter<B, C = Crc8> {
+    inner: B,
+    checksum: C,
+}
+
+impl<B: WriteBuffer, C: Checksum> CrcWriter<B, C> {
+    pub fn new(inner: B, checksum: C) -> Self {
+        Self { inner, checksum }
+    }
+
+    /// The checksum of all the data successfully pushed so far.
+    pub fn finalize(&self) -> C::Output {
+        self.checksum.finalize()
+    }
+
+    /// Consumes the writer, returning the wrapped buffer.
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B: WriteBuffer, C: Checksum> WriteBuffer for CrcWriter<B, C> {
+    fn push_slice(&mut self, bf: &[u8]) -> Result<(), UcPackError> {
+        self.inner.push_slice(bf)?;
+        self.checksum.update(bf);
+        Ok(())
+    }
+
+    #[inline]
+    fn bytes_written(&self) -> usize {
+        self.inner.bytes_written()
+    }
+
+    #[inline]
+    fn capacity(&self) -> Option<usize> {
+        self.inner.capacity()
+    }
+}
+
+/// The single byte [crc8](crate::crc8) used by the other ucpack implementations,
+/// or any other CRC-8 variant (see [Crc8::with_config]).
+///
+/// The crc can be computed incrementally, such as over data arriving in chunks,
+/// and being `Copy` intermediate states can be snapshotted:
+/// ```rust
+/// use ucpack::checksum::Crc8;
+///
+/// let mut crc = Crc8::new();
+/// crc.update(b"1234");
+/// let snapshot = crc;
+///
+/// crc.update(b"56789");
+/// assert_eq!(crc.finalize(), ucpack::crc8_slice(b"123456789"));
+/// assert_eq!(snapshot.finalize(), ucpack::crc8_slice(b"1234"));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Crc8 {
+    config: Crc8Config,
+    crc: u8,
+}
+
+impl Crc8 {
+    pub const fn new() -> Self {
+        Self::with_config(Crc8Config::MAXIM)
+    }
+
+    /// A CRC-8 with the given parameters, for example to talk to devices using CRC-8/SMBUS:
+    /// ```rust
+    /// use ucpack::{checksum::{Crc8, Crc8Config}, UcPack};
+    ///
+    /// let ucpack = UcPack::default().with_checksum(Crc8::with_config(Crc8Config::SMBUS));
+    /// ```
+    pub const fn with_config(config: Crc8Config) -> Self {
+        Self {
+            config,
+            crc: config.initial(),
+        }
+    }
+
+    /// Feeds `data` to the crc.
+    pub fn update(&mut self, data: &[u8]) {
+        self.update_iter(data.iter().copied());
+    }
+
+    /// Feeds a single byte to the crc.
+    pub fn update_byte(&mut self, byte: u8) {
+        self.update_iter([byte]);
+    }
+
+    pub(crate) fn update_iter(&mut self, data: impl IntoIterator<Item = u8>) {
+        self.crc = self.config.update(self.crc, data);
+    }
+
+    /// Returns the crc of all the data fed so far.
+    /// The hasher can still be updated afterwards.
+    pub fn finalize(&self) -> u8 {
+        self.config.finalize(self.crc)
+    }
+}
+
+impl Checksum for Crc8 {
+    type Output = [u8; 1];
+    const SIZE: usize = 1;
+
+    fn update(&mut self, data: &[u8]) {
+        Crc8::update(self, data);
+    }
+
+    fn finalize(&self) -> [u8; 1] {
+        [Crc8::finalize(self)]
+    }
+}
+
+/// Parameters of a CRC-8 variant, as found in the
+/// [catalogue](https://reveng.sourceforge.io/crc-catalogue/1-15.htm#crc.cat-bits.8).
+///
+/// `poly` is given in its normal (MSB-first) form, even for reflected variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crc8Config {
+    pub poly: u8,
+    pub init: u8,
+    pub xorout: u8,
+    pub reflect_in: bool,
+    pub reflect_out: bool,
+}
+
+impl Crc8Config {
+    /// CRC-8/MAXIM-DOW, the one used by the ucpack protocol.
+    pub const MAXIM: Self = Self {
+        poly: 0x31,
+        init: 0x00,
+        xorout: 0x00,
+        reflect_in: true,
+        reflect_out: true,
+    };
+
+    /// CRC-8/SMBUS.
+    pub const SMBUS: Self = Self {
+        poly: 0x07,
+        init: 0x00,
+        xorout: 0x00,
+        reflect_in: false,
+        reflect_out: false,
+    };
+
+    /// The initial value of the running crc.
+    ///
+    /// The running crc of reflected variants is kept reflected, so that
+    /// input bytes don't have to be reversed one by one.
+    pub(crate) const fn initial(&self) -> u8 {
+        match self.reflect_in {
+            true => self.init.reverse_bits(),
+            false => self.init,
+        }
+    }
+
+    /// Feeds `data` to a running crc
+    pub(crate) fn update(&self, crc: u8, data: impl IntoIterator<Item = u8>) -> u8 {
+        #[cfg(feature = "crc-table")]
+        if self.poly == Self::MAXIM.poly && self.reflect_in == Self::MAXIM.reflect_in {
+            return data
+                .into_iter()
+                .fold(crc, |crc, byte| MAXIM_TABLE[usize::from(crc ^ byte)]);
+        }
+
+        data.into_iter()
+            .fold(crc, |crc, byte| self.update_bitwise(crc, byte))
+    }
+
+    /// Feeds a single byte to a running crc, one bit at a time
+    const fn update_bitwise(&self, crc: u8, byte: u8) -> u8 {
+        let mut crc = crc ^ byte;
+        let mut i = 0;
+        while i < 8 {
+            crc = match (self.reflect_in, crc & 0x01, crc & 0x80) {
+                (true, 0, _) => crc >> 1,
+                (true, _, _) => (crc >> 1) ^ self.poly.reverse_bits(),
+                (false, _, 0) => crc << 1,
+                (false, _, _) => (crc << 1) ^ self.poly,
+            };
+            i += 1;
+        }
+
+        crc
+    }
+
+    /// Lookup table feeding a whole byte at once: the running crc
+    /// becomes `table[crc ^ byte]`
+    #[cfg(any(feature = "crc-table", test))]
+    const fn table(&self) -> [u8; 256] {
+        let mut table = [0; 256];
+        let mut i = 0;
+        while i < table.len() {
+            table[i] = self.update_bitwise(0, i as u8);
+            i += 1;
+        }
+
+        table
+    }
+
+    /// Turns a running crc into the final checksum
+    pub(crate) const fn finalize(&self, crc: u8) -> u8 {
+        let crc = match self.reflect_in != self.reflect_out {
+            true => crc.reverse_bits(),
+            false => crc,
+        };
+
+        crc ^ self.xorout
+    }
+}
+
+/// Trades 256 bytes of flash for a much faster [crc8](crate::crc8)
+#[cfg(feature = "crc-table")]
+static MAXIM_TABLE: [u8; 256] = Crc8Config::MAXIM.table();
+
+impl Default for Crc8Config {
+    fn default() -> Self {
+        Self::MAXIM
+    }
+}
+
+/// CRC-16/CCITT-FALSE (see [crc16]), appended as a big-endian `u16`.
+///
+/// Frames using it are NOT compatible with the other ucpack implementations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crc16 {
+    crc: u16,
+}
+
+impl Crc16 {
+    pub const fn new() -> Self {
+        Self { crc: 0xFFFF }
+    }
+}
+
+impl Default for Crc16 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checksum for Crc16 {
+    type Output = [u8; 2];
+    const SIZE: usize = 2;
+
+    fn update(&mut self, data: &[u8]) {
+        self.crc = data.iter().copied().fold(self.crc, crc16_update);
+    }
+
+    fn finalize(&self) -> [u8; 2] {
+        self.crc.to_be_bytes()
+    }
+}
+
+/// Feeds a single byte to a running crc16
+fn crc16_update(crc: u16, byte: u8) -> u16 {
+    (0..8).fold(crc ^ (u16::from(byte) << 8), |crc, _| match crc & 0x8000 {
+        0 => crc << 1,
+        _ => (crc << 1) ^ 0x1021,
+    })
+}
+
+/// Helper function to calculate crc16 over byte slices
+#[inline]
+pub fn crc16_slice(input: &[u8]) -> u16 {
+    crc16(input.iter().copied())
+}
+
+/// Calculates a CRC-16/CCITT-FALSE checksum over any `u8` iterator:
+/// polynomial `0x1021`, initial value `0xFFFF`, no reflection nor final XOR.
+pub fn crc16(input: impl IntoIterator<Item = u8>) -> u16 {
+    input.into_iter().fold(0xFFFF, crc16_update)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn crc16_check() {
+        // check values from the CRC RevEng catalogue
+        assert_eq!(crc16_slice(b"123456789"), 0x29B1);
+        assert_eq!(crc16_slice(b""), 0xFFFF);
+    }
+
+    #[test]
+    fn crc8_check() {
+        const fn config(poly: u8, init: u8, xorout: u8, reflect: bool) -> Crc8Config {
+            Crc8Config {
+                poly,
+                init,
+                xorout,
+                reflect_in: reflect,
+                reflect_out: reflect,
+            }
+        }
+
+        // check values from the CRC RevEng catalogue
+        let check = |config| crate::crc8_slice_with(config, b"123456789");
+        assert_eq!(check(Crc8Config::MAXIM), 0xA1);
+        assert_eq!(check(Crc8Config::SMBUS), 0xF4);
+        assert_eq!(check(config(0x07, 0xFF, 0x00, true)), 0xD0); // ROHC
+        assert_eq!(check(config(0x07, 0x00, 0x55, false)), 0xA1); // I-432-1
+        assert_eq!(check(config(0x2F, 0xFF, 0xFF, false)), 0xDF); // AUTOSAR
+
+        assert_eq!(crate::crc8_slice(b"123456789"), 0xA1);
+    }
+
+    #[test]
+    fn crc8_table() {
+        /// The original, bitwise, ucpack crc8
+        fn reference(data: &[u8]) -> u8 {
+            data.iter()
+                .flat_map(|&byte| (0..8).map(move |j| (byte, j)))
+                .fold(0, |crc, (byte, j)| {
+                    let sum = (crc ^ (byte >> j)) & 0x01;
+                    (crc >> 1) ^ if sum != 0 { 0x8C } else { 0 }
+                })
+        }
+
+        let table = Crc8Config::MAXIM.table();
+        let mut buffer = [0u8; 64];
+        let mut seed = 0x2545_F491u32;
+
+        for len in 0..buffer.len() {
+            // xorshift32
+            for byte in &mut buffer[..len] {
+                seed ^= seed << 13;
+                seed ^= seed >> 17;
+                seed ^= seed << 5;
+                *byte = seed as u8;
+            }
+
+            let data = &buffer[..len];
+            let expected = reference(data);
+            assert_eq!(crate::crc8_slice(data), expected);
+            assert_eq!(
+                data.iter()
+                    .fold(0, |crc, &byte| table[usize::from(crc ^ byte)]),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn streaming() {
+        let data = b"123456789";
+        let expected = crate::crc8_slice(data);
+
+        for split in 0..=data.len() {
+            let (first, second) = data.split_at(split);
+
+            let mut crc = Crc8::new();
+            crc.update(first);
+            crc.update(&[]);
+            crc.update(second);
+            assert_eq!(crc.finalize(), expected);
+
+            // a snapshot keeps going on its own
+            let mut snapshot = crc;
+            snapshot.update_byte(0);
+            assert_eq!(crc.finalize(), expected);
+            assert_eq!(
+                snapshot.finalize(),
+                crate::crc8(data.iter().copied().chain([0]))
+            );
+        }
+
+        let mut crc = Crc8::new();
+        for &byte in data {
+            crc.update_byte(byte);
+        }
+        assert_eq!(crc.finalize(), expected);
+        assert_eq!(Checksum::finalize(&crc), [expected]);
+
+        let mut crc = Crc8::with_config(Crc8Config::SMBUS);
+        crc.update(b"1234");
+        crc.update(b"56789");
+        assert_eq!(crc.finalize(), 0xF4);
+
+        let mut crc = Crc16::new();
+        crc.update(b"1234");
+        crc.update(b"56789");
+        assert_eq!(crc.finalize(), [0x29, 0xB1]);
+    }
+}
@@ -0,0 +1,159 @@
+//! [tokio_util::codec] integration, allowing a byte stream (such as a serial port
+//! or a TCP connection) to be wrapped in a [Framed](tokio_util::codec::Framed)
+//! sending and receiving ucpack messages.
+//!
+//! [UcPackCodec] decodes messages of a single type. [UcPack] itself can be used
+//! as a codec too, encoding any message and decoding verified frames, which can
+//! then be deserialized into whichever type they turn out to carry.
+
+use core::marker::PhantomData;
+
+use bytes::{Buf, BytesMut};
+use serde::{de::DeserializeOwned, Serialize};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::{
+    checksum::{Checksum, Crc8},
+    UcPack, UcPackError,
+};
+
+/// A codec encoding and decoding messages of type `T` with the given [UcPack] configuration.
+///
+/// Decoding consumes one whole frame at a time, even when it fails verification
+/// or deserialization, so that a single corrupted frame doesn't stall the stream.
+/// Bytes which don't begin a frame with the configured indices (line noise,
+/// a frame received halfway) are skipped up to the next start index.
+/// ```rust
+/// use tokio_util::codec::{Decoder, Encoder};
+/// use ucpack::{codec::UcPackCodec, UcPack};
+///
+/// let mut codec = UcPackCodec::<(u8, u16)>::new(UcPack::default());
+/// let mut buffer = bytes::BytesMut::new();
+///
+/// codec.encode((1, 2), &mut buffer).unwrap();
+/// assert_eq!(codec.decode(&mut buffer).unwrap(), Some((1, 2)));
+/// ```
+#[derive(Debug)]
+pub struct UcPackCodec<T, C = Crc8> {
+    ucpack: UcPack<C>,
+    _message: PhantomData<fn() -> T>,
+}
+
+impl<T, C> UcPackCodec<T, C> {
+    pub fn new(ucpack: UcPack<C>) -> Self {
+        Self {
+            ucpack,
+            _message: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for UcPackCodec<T> {
+    fn default() -> Self {
+        Self::new(UcPack::default())
+    }
+}
+
+impl<T, C: Checksum> Clone for UcPackCodec<T, C> {
+    fn clone(&self) -> Self {
+        Self::new(self.ucpack)
+    }
+}
+
+impl<T: Serialize, C: Checksum> Encoder<T> for UcPackCodec<T, C> {
+    type Error = UcPackError;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        encode(&self.ucpack, &item, dst)
+    }
+}
+
+impl<T: DeserializeOwned, C: Checksum> Decoder for UcPackCodec<T, C> {
+    type Item = T;
+    type Error = UcPackError;
+
+    /// Returns `Ok(None)` until a whole frame has been received.
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let Some(frame) = split_frame(&self.ucpack, src) else {
+            return Ok(None);
+        };
+
+        self.ucpack.deserialize_slice(&frame).map(Some)
+    }
+}
+
+impl<T: Serialize, C: Checksum> Encoder<T> for UcPack<C> {
+    type Error = UcPackError;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        encode(self, &item, dst)
+    }
+}
+
+/// Decodes whole frames, verified but not deserialized.
+///
+/// Just like [UcPackCodec], a frame failing verification is consumed anyway,
+/// and bytes which don't begin a frame are skipped.
+/// ```rust
+/// use tokio_util::codec::{Decoder, Encoder};
+/// use ucpack::UcPack;
+///
+/// let mut ucpack = UcPack::default();
+/// let mut buffer = bytes::BytesMut::new();
+///
+/// ucpack.encode((1u8, 2u16), &mut buffer).unwrap();
+/// let frame = ucpack.decode(&mut buffer).unwrap().unwrap();
+/// assert_eq!(ucpack.deserialize_slice::<(u8, u16)>(&frame).unwrap(), (1, 2));
+/// ```
+impl<C: Checksum> Decoder for UcPack<C> {
+    type Item = BytesMut;
+    type Error = UcPackError;
+
+    /// Returns `Ok(None)` until a whole frame has been received.
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let Some(frame) = split_frame(self, src) else {
+            return Ok(None);
+        };
+
+        self.verify(&frame).map(|_| Some(frame))
+    }
+}
+
+/// Splits the frame `src` begins with off it, once complete (see [UcPack::is_complete_message]).
+///
+/// Leading bytes which don't begin a frame with the right indices are discarded,
+/// up to the next start index.
+fn split_frame<C: Checksum>(ucpack: &UcPack<C>, src: &mut BytesMut) -> Option<BytesMut> {
+    loop {
+        match ucpack.is_complete_message(src) {
+            Ok(frame) => return Some(src.split_to(frame.len())),
+            Err(UcPackError::WrongIndex { .. }) => {
+                let next = src[1..]
+                    .iter()
+                    .position(|&byte| byte == ucpack.start_index)
+                    .map_or(src.len(), |offset| offset + 1);
+                src.advance(next);
+            }
+            // incomplete frame
+            Err(_) => return None,
+        }
+    }
+}
+
+/// Appends the frame of `item` to `dst`, leaving it untouched on failure
+fn encode<T: Serialize, C: Checksum>(
+    ucpack: &UcPack<C>,
+    item: &T,
+    dst: &mut BytesMut,
+) -> Result<(), UcPackError> {
+    let size = ucpack.serialize_size(item)?;
+    let start = dst.len();
+
+    dst.resize(start + size, 0);
+    if let Err(err) = ucpack.serialize_slice(item, &mut dst[start..]) {
+        dst.truncate(start);
+        return Err(err);
+    }
+
+    Ok(())
+}
@@ -1,50 +1,210 @@
 use serde::de::{self, EnumAccess, IntoDeserializer, SeqAccess, VariantAccess};
 
-use crate::{buffer::ReadBuffer, macros::unimpl, macros::unimpl_de, UcPackError};
+use crate::{buffer::ReadBuffer, macros::unimpl, macros::unimpl_de, tag, UcPackError};
 
 /// A `serde` compatible Deserializer which works
 /// on a [ReadBuffer]
-pub struct Deserializer<B: ReadBuffer> {
+pub struct Deserializer<B> {
     buffer: B,
+    tagged: bool,
+    wide_variants: bool,
 }
 
-impl<B: ReadBuffer> Deserializer<B> {
+impl<B> Deserializer<B> {
     pub fn new(buffer: B) -> Self {
-        Self { buffer }
+        Self {
+            buffer,
+            tagged: false,
+            wide_variants: false,
+        }
+    }
+
+    /// Enables or disables the tagged flavor of the format, where every value
+    /// is preceded by its [type tag](crate::tag).
+    pub fn with_tagged(self, tagged: bool) -> Self {
+        Self { tagged, ..self }
+    }
+
+    /// Reads enum variant indices as little-endian `u16`s instead of a single byte.
+    pub fn with_wide_variants(self, wide_variants: bool) -> Self {
+        Self {
+            wide_variants,
+            ..self
+        }
+    }
+}
+
+impl<'de, B: ReadBuffer<'de>> Deserializer<B> {
+    /// Number of bytes left to be deserialized in the underlying buffer.
+    ///
+    /// Useful for optional trailing fields: if anything is left after
+    /// the known fields, a newer version of the message is being read.
+    /// `usize::MAX` if the buffer can't tell, see [ReadBuffer::remaining_bytes].
+    pub fn remaining_bytes(&self) -> usize {
+        self.buffer.remaining_bytes()
+    }
+
+    /// Discards the next `n` bytes of the buffer.
+    ///
+    /// Since the ucpack format isn't self-describing, ignored values
+    /// (see [de::IgnoredAny]) don't consume any data by themselves: use this
+    /// to manually skip over data you know the size of.
+    pub fn skip_bytes(&mut self, n: usize) -> Result<(), UcPackError> {
+        self.buffer.skip_n(n)
+    }
+
+    /// Returns the next byte of the buffer without consuming it, such as
+    /// a discriminator preceding the value, see [ReadBuffer::peek_u8].
+    pub fn peek_u8(&self) -> Result<u8, UcPackError> {
+        self.buffer.peek_u8()
+    }
+
+    /// Consumes the type tag of the next value, checking it matches `tag`.
+    /// Does nothing in the untagged format.
+    fn expect_tag(&mut self, tag: u8) -> Result<(), UcPackError> {
+        if self.tagged && self.buffer.read_u8()? != tag {
+            return Err(UcPackError::InvalidData);
+        }
+
+        Ok(())
+    }
+
+    /// Consumes the header of a sequence, returning its length.
+    /// In the untagged format the length is implied, so `len` is returned as is.
+    fn expect_seq(&mut self, len: usize) -> Result<usize, UcPackError> {
+        if !self.tagged {
+            return Ok(len);
+        }
+
+        self.expect_tag(tag::SEQ)?;
+        match usize::from(self.buffer.read_u8()?) {
+            found if found == len => Ok(len),
+            _ => Err(UcPackError::InvalidData),
+        }
+    }
+
+    fn read_bool(&mut self) -> Result<bool, UcPackError> {
+        match self.buffer.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(UcPackError::InvalidData),
+        }
     }
 
     fn read_u16(&mut self) -> Result<u16, UcPackError> {
         self.buffer.read_n().map(u16::from_le_bytes)
     }
+
+    fn read_variant_index(&mut self) -> Result<u16, UcPackError> {
+        match self.wide_variants {
+            true => self.read_u16(),
+            false => self.buffer.read_u8().map(u16::from),
+        }
+    }
+
+    fn read_f32(&mut self) -> Result<f32, UcPackError> {
+        self.buffer.read_n().map(f32::from_le_bytes)
+    }
+
+    /// Reads a byte array out of buffers which can't lend it. Kept out of line
+    /// so that its scratch space is only reserved when actually needed.
+    #[inline(never)]
+    fn visit_copied_array<V>(&mut self, len: usize, visitor: V) -> Result<V::Value, UcPackError>
+    where
+        V: de::Visitor<'de>,
+    {
+        let mut scratch = [0u8; crate::byte_array::MAX_BULK_LEN];
+        let copy = &mut scratch[..len];
+        self.buffer.read_slice(copy)?;
+        visitor.visit_bytes(copy)
+    }
+
+    /// Reads a byte array prefixed by its length, borrowing it from the buffer
+    /// if possible, or copying it out of line otherwise.
+    fn visit_bytes<V>(&mut self, visitor: V) -> Result<V::Value, UcPackError>
+    where
+        V: de::Visitor<'de>,
+    {
+        let len = self.buffer.read_u8()?.into();
+        match self.buffer.read_borrowed(len) {
+            Err(UcPackError::NoSupport(_)) => self.visit_copied_array(len, visitor),
+            borrowed => visitor.visit_borrowed_bytes(borrowed?),
+        }
+    }
+
+    /// Reads a string prefixed by its length, borrowing it from the buffer
+    /// if possible, or copying it out of line otherwise.
+    fn visit_str<V>(&mut self, visitor: V) -> Result<V::Value, UcPackError>
+    where
+        V: de::Visitor<'de>,
+    {
+        let len = self.buffer.read_u8()?.into();
+        match self.buffer.read_borrowed(len) {
+            Err(UcPackError::NoSupport(_)) => self.visit_copied_str(len, visitor),
+            borrowed => visitor.visit_borrowed_str(from_utf8(borrowed?)?),
+        }
+    }
+
+    /// See [Deserializer::visit_copied_array].
+    #[inline(never)]
+    fn visit_copied_str<V>(&mut self, len: usize, visitor: V) -> Result<V::Value, UcPackError>
+    where
+        V: de::Visitor<'de>,
+    {
+        let mut scratch = [0u8; u8::MAX as usize];
+        let copy = &mut scratch[..len];
+        self.buffer.read_slice(copy)?;
+        visitor.visit_str(from_utf8(copy)?)
+    }
 }
 
-impl<'de, 'a, B: ReadBuffer> de::Deserializer<'de> for &'a mut Deserializer<B> {
+impl<'de, B: ReadBuffer<'de>> de::Deserializer<'de> for &mut Deserializer<B> {
     type Error = UcPackError;
 
-    fn deserialize_any<V>(self, _: V) -> Result<V::Value, Self::Error>
+    /// Only supported by the tagged format, where the type of the
+    /// next value is known thanks to its tag.
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unimpl!(name = "any")
+        if !self.tagged {
+            return Err(UcPackError::NotSelfDescribing);
+        }
+
+        match self.buffer.read_u8()? {
+            tag::UNIT => visitor.visit_unit(),
+            tag::BOOL => visitor.visit_bool(self.read_bool()?),
+            tag::U8 => visitor.visit_u8(self.buffer.read_u8()?),
+            tag::I8 => visitor.visit_i8(self.buffer.read_u8()? as i8),
+            tag::U16 => visitor.visit_u16(self.read_u16()?),
+            tag::I16 => visitor.visit_i16(self.read_u16()? as i16),
+            tag::F32 => visitor.visit_f32(self.read_f32()?),
+            tag::SEQ => {
+                let len = self.buffer.read_u8()?.into();
+                visitor.visit_seq(SeriesAccess::new(self, len))
+            }
+            tag::BYTES => self.visit_bytes(visitor),
+            tag::STR => self.visit_str(visitor),
+            tag::VARIANT => visitor.visit_enum(EnumVariants::new(self, None)),
+            tag::NONE => visitor.visit_none(),
+            tag::SOME => visitor.visit_some(self),
+            _ => Err(UcPackError::InvalidData),
+        }
     }
 
     fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        let a = match self.buffer.read_u8()? {
-            0 => false,
-            1 => true,
-            _ => return Err(UcPackError::InvalidData),
-        };
-
-        visitor.visit_bool(a)
+        self.expect_tag(tag::BOOL)?;
+        visitor.visit_bool(self.read_bool()?)
     }
 
     fn deserialize_i8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
+        self.expect_tag(tag::I8)?;
         visitor.visit_i8(self.buffer.read_u8()? as i8)
     }
 
@@ -52,6 +212,7 @@ impl<'de, 'a, B: ReadBuffer> de::Deserializer<'de> for &'a mut Deserializer<B> {
     where
         V: de::Visitor<'de>,
     {
+        self.expect_tag(tag::I16)?;
         visitor.visit_i16(self.read_u16()? as i16)
     }
 
@@ -62,6 +223,7 @@ impl<'de, 'a, B: ReadBuffer> de::Deserializer<'de> for &'a mut Deserializer<B> {
     where
         V: de::Visitor<'de>,
     {
+        self.expect_tag(tag::U8)?;
         visitor.visit_u8(self.buffer.read_u8()?)
     }
 
@@ -69,6 +231,7 @@ impl<'de, 'a, B: ReadBuffer> de::Deserializer<'de> for &'a mut Deserializer<B> {
     where
         V: de::Visitor<'de>,
     {
+        self.expect_tag(tag::U16)?;
         visitor.visit_u16(self.read_u16()?)
     }
 
@@ -79,24 +242,80 @@ impl<'de, 'a, B: ReadBuffer> de::Deserializer<'de> for &'a mut Deserializer<B> {
     where
         V: de::Visitor<'de>,
     {
-        let float = self.buffer.read_n().map(f32::from_le_bytes)?;
-        visitor.visit_f32(float)
+        self.expect_tag(tag::F32)?;
+        visitor.visit_f32(self.read_f32()?)
     }
 
     unimpl_de!(deserialize_f64, f64);
     unimpl_de!(deserialize_char, char);
-    unimpl_de!(deserialize_str, &str);
-    unimpl_de!(deserialize_string, name = "String");
-    unimpl_de!(deserialize_bytes, &[u8]);
-    unimpl_de!(deserialize_byte_buf, name = "byte_buf");
-    unimpl_de!(deserialize_option, name = "option");
-    unimpl_de!(deserialize_unit, name = "unit");
 
-    fn deserialize_unit_struct<V>(self, name: &'static str, _: V) -> Result<V::Value, Self::Error>
+    /// Strings are borrowed straight from the buffer whenever
+    /// it supports it (see [ReadBuffer::read_borrowed]), or copied otherwise.
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.expect_tag(tag::STR)?;
+        self.visit_str(visitor)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    /// Byte arrays are borrowed straight from the buffer whenever
+    /// it supports it (see [ReadBuffer::read_borrowed]).
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.expect_tag(tag::BYTES)?;
+        self.visit_bytes(visitor)
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        if !self.tagged {
+            unimpl!(name = "option")
+        }
+
+        match self.buffer.read_u8()? {
+            tag::NONE => visitor.visit_none(),
+            tag::SOME => visitor.visit_some(self),
+            _ => Err(UcPackError::InvalidData),
+        }
+    }
+
+    /// Unit takes no space at all in the untagged format, so nothing is read.
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.expect_tag(tag::UNIT)?;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unimpl!(name = name)
+        self.deserialize_unit(visitor)
     }
 
     fn deserialize_newtype_struct<V>(
@@ -110,25 +329,48 @@ impl<'de, 'a, B: ReadBuffer> de::Deserializer<'de> for &'a mut Deserializer<B> {
         visitor.visit_newtype_struct(self)
     }
 
-    unimpl_de!(deserialize_seq, name = "seq");
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        if !self.tagged {
+            unimpl!(name = "seq")
+        }
+
+        self.expect_tag(tag::SEQ)?;
+        let len = self.buffer.read_u8()?.into();
+        visitor.visit_seq(SeriesAccess::new(self, len))
+    }
 
     fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
+        let len = self.expect_seq(len)?;
         visitor.visit_seq(SeriesAccess::new(self, len))
     }
 
+    /// Byte arrays (de)serialized through [crate::byte_array] are read
+    /// all at once in the untagged format.
     fn deserialize_tuple_struct<V>(
         self,
-        _: &'static str,
+        name: &'static str,
         len: usize,
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        self.deserialize_tuple(len, visitor)
+        use crate::byte_array::{MAX_BULK_LEN, NAME};
+
+        if name != NAME || self.tagged || len > MAX_BULK_LEN {
+            return self.deserialize_tuple(len, visitor);
+        }
+
+        match self.buffer.read_borrowed(len) {
+            Err(UcPackError::NoSupport(_)) => self.visit_copied_array(len, visitor),
+            borrowed => visitor.visit_borrowed_bytes(borrowed?),
+        }
     }
 
     unimpl_de!(deserialize_map, name = "map");
@@ -148,47 +390,76 @@ impl<'de, 'a, B: ReadBuffer> de::Deserializer<'de> for &'a mut Deserializer<B> {
     fn deserialize_enum<V>(
         self,
         _: &'static str,
-        _: &'static [&'static str],
+        variants: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_enum(self)
+        self.expect_tag(tag::VARIANT)?;
+        visitor.visit_enum(EnumVariants::new(self, Some(variants.len())))
     }
 
+    /// Identifiers are encoded by their index, just like variant indices.
+    ///
+    /// The index is visited as a `u64`, which is what serde's `field_identifier` and
+    /// `variant_identifier` visitors (and the ones generated by `#[derive(Deserialize)]`)
+    /// map to the corresponding field or variant, even though they'd prefer strings
+    /// on self-describing formats.
+    ///
+    /// In the tagged format both a `u8` and a unit variant (which is how adjacently
+    /// tagged enums serialize their tag) are accepted.
     fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        self.deserialize_u8(visitor)
+        if self.tagged {
+            match self.buffer.read_u8()? {
+                tag::U8 => return visitor.visit_u64(self.buffer.read_u8()?.into()),
+                tag::VARIANT => {
+                    let index = self.read_variant_index()?;
+                    self.expect_tag(tag::UNIT)?;
+                    return visitor.visit_u64(index.into());
+                }
+                _ => return Err(UcPackError::InvalidData),
+            }
+        }
+
+        visitor.visit_u64(self.read_variant_index()?.into())
     }
 
-    fn deserialize_ignored_any<V>(self, _: V) -> Result<V::Value, Self::Error>
+    /// In the untagged format there's no way of knowing how much data
+    /// an ignored value spans: nothing is consumed and unit is visited instead.
+    ///
+    /// In the tagged format the whole value is consumed.
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unimpl!(name = "ignored type")
+        match self.tagged {
+            true => self.deserialize_any(visitor),
+            false => visitor.visit_unit(),
+        }
     }
 }
 
-impl<'a, 'de, B: ReadBuffer> VariantAccess<'a> for &'de mut Deserializer<B> {
+impl<'de, B: ReadBuffer<'de>> VariantAccess<'de> for &mut Deserializer<B> {
     type Error = UcPackError;
 
     fn unit_variant(self) -> Result<(), Self::Error> {
-        Ok(())
+        self.expect_tag(tag::UNIT)
     }
 
     fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
     where
-        T: de::DeserializeSeed<'a>,
+        T: de::DeserializeSeed<'de>,
     {
         seed.deserialize(self)
     }
 
     fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
     where
-        V: de::Visitor<'a>,
+        V: de::Visitor<'de>,
     {
         de::Deserializer::deserialize_tuple(self, len, visitor)
     }
@@ -199,32 +470,55 @@ impl<'a, 'de, B: ReadBuffer> VariantAccess<'a> for &'de mut Deserializer<B> {
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
-        V: de::Visitor<'a>,
+        V: de::Visitor<'de>,
     {
         de::Deserializer::deserialize_tuple(self, fields.len(), visitor)
     }
 }
 
-impl<'a, 'de, B: ReadBuffer> EnumAccess<'a> for &'de mut Deserializer<B> {
+fn from_utf8(bytes: &[u8]) -> Result<&str, UcPackError> {
+    core::str::from_utf8(bytes).map_err(|_| UcPackError::InvalidData)
+}
+
+struct EnumVariants<'a, B> {
+    deserializer: &'a mut Deserializer<B>,
+    /// Number of variants of the enum, if known
+    count: Option<usize>,
+}
+
+impl<'a, B> EnumVariants<'a, B> {
+    fn new(deserializer: &'a mut Deserializer<B>, count: Option<usize>) -> Self {
+        Self {
+            deserializer,
+            count,
+        }
+    }
+}
+
+impl<'a, 'de, B: ReadBuffer<'de>> EnumAccess<'de> for EnumVariants<'a, B> {
     type Error = UcPackError;
-    type Variant = Self;
+    type Variant = &'a mut Deserializer<B>;
 
     fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
     where
-        V: de::DeserializeSeed<'a>,
+        V: de::DeserializeSeed<'de>,
     {
-        let variant = self.buffer.read_u8()?;
-        let v = seed.deserialize(variant.into_deserializer())?;
-        Ok((v, self))
+        let index = self.deserializer.read_variant_index()?;
+        if self.count.is_some_and(|count| usize::from(index) >= count) {
+            return Err(UcPackError::UnknownVariant { index });
+        }
+
+        let v = seed.deserialize(IntoDeserializer::<UcPackError>::into_deserializer(index))?;
+        Ok((v, self.deserializer))
     }
 }
 
-struct SeriesAccess<'a, B: ReadBuffer + 'a> {
+struct SeriesAccess<'a, B> {
     deserializer: &'a mut Deserializer<B>,
     remaining: usize,
 }
 
-impl<'a, B: ReadBuffer + 'a> SeriesAccess<'a, B> {
+impl<'a, B> SeriesAccess<'a, B> {
     fn new(deserializer: &'a mut Deserializer<B>, len: usize) -> Self {
         Self {
             deserializer,
@@ -233,15 +527,15 @@ impl<'a, B: ReadBuffer + 'a> SeriesAccess<'a, B> {
     }
 }
 
-impl<'a, 'seq, B: ReadBuffer> SeqAccess<'seq> for SeriesAccess<'a, B> {
+impl<'de, B: ReadBuffer<'de>> SeqAccess<'de> for SeriesAccess<'_, B> {
     type Error = UcPackError;
 
     fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
     where
-        T: de::DeserializeSeed<'seq>,
+        T: de::DeserializeSeed<'de>,
     {
         // check if remaining
-        if self.remaining <= 0 {
+        if self.remaining == 0 {
             return Ok(None);
         }
 
@@ -249,3 +543,57 @@ impl<'a, 'seq, B: ReadBuffer> SeqAccess<'seq> for SeriesAccess<'a, B> {
         seed.deserialize(&mut *self.deserializer).map(Some)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use serde::Deserialize;
+
+    use super::Deserializer;
+    use crate::buffer::SliceCursor;
+
+    #[test]
+    fn skip_bytes() {
+        let mut cursor = SliceCursor::from_slice(&[1, 2, 3, 4, 5][..]);
+        let mut de = Deserializer::new(&mut cursor);
+
+        de.skip_bytes(3).unwrap();
+        assert_eq!(
+            u16::deserialize(&mut de).unwrap(),
+            u16::from_le_bytes([4, 5])
+        );
+        de.skip_bytes(1).unwrap_err();
+    }
+
+    #[test]
+    fn remaining_bytes() {
+        let mut cursor = SliceCursor::from_slice(&[1, 2, 3, 1, 5, 6][..]);
+        let mut de = Deserializer::new(&mut cursor);
+        assert_eq!(de.remaining_bytes(), 6);
+
+        u8::deserialize(&mut de).unwrap();
+        assert_eq!(de.remaining_bytes(), 5);
+
+        i16::deserialize(&mut de).unwrap();
+        assert_eq!(de.remaining_bytes(), 3);
+
+        <(bool, u16)>::deserialize(&mut de).unwrap();
+        assert_eq!(de.remaining_bytes(), 0);
+
+        u8::deserialize(&mut de).unwrap_err();
+        assert_eq!(de.remaining_bytes(), 0);
+    }
+
+    #[test]
+    fn peek_u8() {
+        let mut cursor = SliceCursor::from_slice(&[2, 1, 0][..]);
+        let mut de = Deserializer::new(&mut cursor);
+
+        // a leading discriminator selecting the type of the value
+        let value = match de.peek_u8().unwrap() {
+            1 => u16::from(<(u8, u8)>::deserialize(&mut de).unwrap().1),
+            _ => <(u8, u16)>::deserialize(&mut de).unwrap().1,
+        };
+        assert_eq!(value, 1);
+        assert_eq!(de.remaining_bytes(), 0);
+    }
+}
@@ -0,0 +1,438 @@
+use crate::{
+    checksum::{Checksum, Crc8},
+    frame_size, UcPack,
+};
+
+/// Buffers bytes received one at a time until they form a whole frame,
+/// using a fixed buffer of `N` bytes.
+///
+/// Bytes are discarded until the start index is received, then buffered until
+/// the whole frame (as indicated by its length byte) has arrived. Frames which
+/// can't fit in `N` bytes are dropped.
+///
+/// Frames aren't verified in any way: see [Decoder] for that.
+pub struct Accumulator<const N: usize> {
+    start_index: u8,
+    extended_length: bool,
+    checksum_size: usize,
+    buffer: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Accumulator<N> {
+    pub const fn new(start_index: u8) -> Self {
+        Self {
+            start_index,
+            extended_length: false,
+            checksum_size: 1,
+            buffer: [0; N],
+            len: 0,
+        }
+    }
+
+    /// Expects frames with a `u16` length, see [UcPack::with_extended_length].
+    pub const fn with_extended_length(self, extended_length: bool) -> Self {
+        Self {
+            extended_length,
+            ..self
+        }
+    }
+
+    /// Expects frames ending with a checksum of `checksum_size` bytes
+    /// instead of a single one, see [UcPack::with_checksum].
+    pub const fn with_checksum_size(self, checksum_size: usize) -> Self {
+        Self {
+            checksum_size,
+            ..self
+        }
+    }
+
+    /// Feeds a single byte to the accumulator.
+    ///
+    /// Returns the whole frame once its last byte is pushed,
+    /// starting over afterwards.
+    pub fn push(&mut self, byte: u8) -> Option<&[u8]> {
+        if self.len == 0 && byte != self.start_index {
+            return None;
+        }
+
+        if self.len == N {
+            self.reset();
+            return None;
+        }
+
+        self.buffer[self.len] = byte;
+        self.len += 1;
+
+        let total_size = frame_size(
+            &self.buffer[..self.len],
+            self.extended_length,
+            self.checksum_size,
+        )?;
+        if total_size > N {
+            self.reset();
+            return None;
+        }
+
+        if self.len < total_size {
+            return None;
+        }
+
+        self.reset();
+        Some(&self.buffer[..total_size])
+    }
+
+    /// Discards any partially received frame, waiting for the next start index.
+    pub fn reset(&mut self) {
+        self.len = 0;
+    }
+
+    /// Number of bytes of the current frame received so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the accumulator is waiting for the start of a frame.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// Reassembles frames received one byte at a time, such as from an UART RX interrupt,
+/// using a fixed buffer of `N` bytes.
+///
+/// Works just like an [Accumulator], but frames failing verification are dropped as well.
+/// Their bytes are then scanned again for the next start index, so that a garbage byte
+/// equal to the start index can't swallow the frame beginning right after it.
+/// ```rust
+/// use ucpack::{decoder::Decoder, UcPack};
+///
+/// let ucpack = UcPack::default();
+/// let mut decoder = Decoder::<64>::new(ucpack);
+///
+/// let mut frame = [0u8; 16];
+/// let n = ucpack.serialize_slice(&(1u8, 2u16), &mut frame).unwrap();
+///
+/// for &byte in &frame[..n - 1] {
+///     assert!(decoder.push(byte).is_none());
+/// }
+///
+/// let packet = decoder.push(frame[n - 1]).unwrap();
+/// assert_eq!(ucpack.deserialize_slice::<(u8, u16)>(packet).unwrap(), (1, 2));
+/// ```
+pub struct Decoder<const N: usize, C = Crc8> {
+    frames: PacketFramer<N, C>,
+}
+
+/// Another name for [Decoder], fed through [Decoder::feed].
+pub type StreamDecoder<const N: usize, C = Crc8> = Decoder<N, C>;
+
+impl<const N: usize, C: Checksum> Decoder<N, C> {
+    pub const fn new(ucpack: UcPack<C>) -> Self {
+        Self {
+            frames: PacketFramer::new(ucpack),
+        }
+    }
+
+    /// Feeds a single byte to the decoder.
+    ///
+    /// Returns the whole verified frame once its last byte is pushed, which can then be
+    /// passed to [UcPack::deserialize_slice]. The decoder starts over afterwards.
+    pub fn push(&mut self, byte: u8) -> Option<&[u8]> {
+        self.frames.push(byte)
+    }
+
+    /// Same as [Decoder::push].
+    #[inline]
+    pub fn feed(&mut self, byte: u8) -> Option<&[u8]> {
+        self.push(byte)
+    }
+
+    /// Discards any partially received frame, waiting for the next start index.
+    pub fn reset(&mut self) {
+        self.frames.reset();
+    }
+
+    /// Number of bytes of the current frame received so far.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Whether the decoder is waiting for the start of a frame.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+/// Extracts verified frames out of a byte stream fed in chunks of any size,
+/// such as the contents of a drained DMA or UART buffer, using a fixed buffer of `BUF` bytes.
+///
+/// Bytes are buffered across calls to [PacketFramer::feed_slice], so frames can
+/// span multiple chunks. Garbage between frames is skipped, while frames failing
+/// verification are counted (see [PacketFramer::corrupt_count]) and resynchronized
+/// from, looking for the next start index after theirs.
+/// ```rust
+/// use ucpack::{decoder::PacketFramer, UcPack};
+///
+/// let ucpack = UcPack::default();
+/// let mut framer = PacketFramer::<64>::new(ucpack);
+///
+/// let mut stream = [0u8; 14];
+/// let n = ucpack.serialize_slice(&(1u8, 2u16), &mut stream).unwrap();
+/// ucpack.serialize_slice(&(3u8, 4u16), &mut stream[n..]).unwrap();
+/// let (first, second) = stream.split_at(9);
+///
+/// let mut packets = framer.feed_slice(first);
+/// let packet = packets.next_packet().unwrap();
+/// assert_eq!(ucpack.deserialize_slice::<(u8, u16)>(packet).unwrap(), (1, 2));
+/// assert!(packets.next_packet().is_none());
+///
+/// let mut packets = framer.feed_slice(second);
+/// let packet = packets.next_packet().unwrap();
+/// assert_eq!(ucpack.deserialize_slice::<(u8, u16)>(packet).unwrap(), (3, 4));
+/// ```
+pub struct PacketFramer<const BUF: usize, C = Crc8> {
+    ucpack: UcPack<C>,
+    buffer: [u8; BUF],
+    len: usize,
+    /// Size of the frame last yielded, still at the beginning of `buffer`
+    yielded: usize,
+    corrupt_count: u32,
+}
+
+impl<const BUF: usize, C: Checksum> PacketFramer<BUF, C> {
+    pub const fn new(ucpack: UcPack<C>) -> Self {
+        Self {
+            ucpack,
+            buffer: [0; BUF],
+            len: 0,
+            yielded: 0,
+            corrupt_count: 0,
+        }
+    }
+
+    /// Feeds a chunk of the stream to the framer, returning the frames it completes.
+    ///
+    /// `bytes` are consumed as frames are pulled out of the returned [PacketFramerResult],
+    /// so any bytes left when it's dropped early are discarded.
+    #[doc(alias = "feed")]
+    pub fn feed_slice<'a>(&'a mut self, bytes: &'a [u8]) -> PacketFramerResult<'a, BUF, C> {
+        PacketFramerResult {
+            framer: self,
+            input: bytes,
+        }
+    }
+
+    /// Number of frames dropped so far because they failed verification.
+    pub fn corrupt_count(&self) -> u32 {
+        self.corrupt_count
+    }
+
+    /// Discards any buffered data, waiting for the next start index.
+    pub fn reset(&mut self) {
+        self.len = 0;
+        self.yielded = 0;
+    }
+
+    /// Number of bytes buffered, waiting for the rest of their frame.
+    pub fn len(&self) -> usize {
+        self.len - self.yielded
+    }
+
+    /// Whether the framer is waiting for the start of a frame.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Feeds a single byte, returning the frame it completes, see [Decoder::push].
+    ///
+    /// A corrupted frame may hide a valid one which is already complete, in which case
+    /// the latter is returned instead, while any one following it is only returned
+    /// along with the next byte.
+    fn push(&mut self, byte: u8) -> Option<&[u8]> {
+        let yielded = core::mem::take(&mut self.yielded);
+        self.discard(yielded);
+
+        if self.len == BUF {
+            self.discard(BUF.min(1));
+        }
+        self.fill(&[byte]);
+
+        let size = self.buffered_frame()?;
+        self.yielded = size;
+        Some(&self.buffer[..size])
+    }
+
+    /// Returns the size of the verified frame at the beginning of the buffer, if it's complete,
+    /// after skipping anything before it.
+    fn buffered_frame(&mut self) -> Option<usize> {
+        loop {
+            let start_index = self.ucpack.start_index;
+            let start = self.buffer[..self.len]
+                .iter()
+                .position(|&byte| byte == start_index)
+                .unwrap_or(self.len);
+            self.discard(start);
+
+            let size = self.ucpack.buffered_frame_size(&self.buffer[..self.len])?;
+            if size > BUF {
+                self.discard(1);
+                continue;
+            }
+
+            if size > self.len {
+                return None;
+            }
+
+            match self.ucpack.check_packet(&self.buffer[..size]) {
+                Ok(_) => return Some(size),
+                Err(_) => {
+                    self.corrupt_count = self.corrupt_count.saturating_add(1);
+                    self.discard(1);
+                }
+            }
+        }
+    }
+
+    /// Buffers as much of `input` as it fits, returning how many bytes were taken
+    fn fill(&mut self, input: &[u8]) -> usize {
+        let n = input.len().min(BUF - self.len);
+        self.buffer[self.len..self.len + n].copy_from_slice(&input[..n]);
+        self.len += n;
+        n
+    }
+
+    fn discard(&mut self, n: usize) {
+        self.buffer.copy_within(n..self.len, 0);
+        self.len -= n;
+    }
+}
+
+/// The frames completed by a chunk fed to a [PacketFramer].
+///
+/// Frames are borrowed from the framer's buffer, which is reused for the following
+/// ones, so this can't be an [Iterator]: pull them with [PacketFramerResult::next_packet].
+pub struct PacketFramerResult<'a, const BUF: usize, C = Crc8> {
+    framer: &'a mut PacketFramer<BUF, C>,
+    input: &'a [u8],
+}
+
+impl<const BUF: usize, C: Checksum> PacketFramerResult<'_, BUF, C> {
+    /// Returns the next verified frame, which can be passed to [UcPack::deserialize_slice],
+    /// or `None` once the chunk has been consumed.
+    pub fn next_packet(&mut self) -> Option<&[u8]> {
+        let framer = &mut *self.framer;
+        let yielded = core::mem::take(&mut framer.yielded);
+        framer.discard(yielded);
+
+        let size = loop {
+            if let Some(size) = framer.buffered_frame() {
+                break size;
+            }
+
+            if self.input.is_empty() {
+                return None;
+            }
+
+            match framer.fill(self.input) {
+                // the frame can't be completed, as it's bigger than the buffer
+                0 if framer.len > 0 => framer.discard(1),
+                0 => self.input = &[],
+                n => self.input = &self.input[n..],
+            }
+        };
+
+        framer.yielded = size;
+        Some(&framer.buffer[..size])
+    }
+}
+
+/// Splits a stream of COBS framed messages (see [UcPack::serialize_cobs_slice])
+/// on their `0x00` delimiters, using a fixed buffer of `N` bytes.
+///
+/// Messages which can't fit in `N` bytes, delimiter included, are dropped.
+/// They aren't verified in any way: [UcPack::deserialize_cobs_slice] does that.
+/// ```rust
+/// use ucpack::{decoder::CobsDecoder, UcPack};
+///
+/// let ucpack = UcPack::default();
+/// let mut decoder = CobsDecoder::<64>::new();
+///
+/// let mut frame = [0u8; 16];
+/// let n = ucpack.serialize_cobs_slice(&(0u8, 2u16), &mut frame).unwrap();
+///
+/// for &byte in &frame[..n - 1] {
+///     assert!(decoder.push(byte).is_none());
+/// }
+///
+/// let message = decoder.push(0).unwrap();
+/// assert_eq!(ucpack.deserialize_cobs_slice::<(u8, u16)>(message).unwrap(), (0, 2));
+/// ```
+#[cfg(feature = "cobs")]
+pub struct CobsDecoder<const N: usize> {
+    buffer: [u8; N],
+    len: usize,
+    overflown: bool,
+}
+
+#[cfg(feature = "cobs")]
+impl<const N: usize> CobsDecoder<N> {
+    pub const fn new() -> Self {
+        Self {
+            buffer: [0; N],
+            len: 0,
+            overflown: false,
+        }
+    }
+
+    /// Feeds a single byte to the decoder.
+    ///
+    /// Returns the whole message, delimiter included, once its delimiter is pushed,
+    /// starting over afterwards. Empty messages are skipped.
+    pub fn push(&mut self, byte: u8) -> Option<&[u8]> {
+        if byte != 0 {
+            // room has to be left for the delimiter
+            match self.len + 1 < N {
+                true => {
+                    self.buffer[self.len] = byte;
+                    self.len += 1;
+                }
+                false => self.overflown = true,
+            }
+
+            return None;
+        }
+
+        let (len, overflown) = (self.len, self.overflown);
+        self.reset();
+        if len == 0 || overflown {
+            return None;
+        }
+
+        self.buffer[len] = 0;
+        Some(&self.buffer[..=len])
+    }
+
+    /// Discards any partially received message, waiting for the next delimiter.
+    pub fn reset(&mut self) {
+        self.len = 0;
+        self.overflown = false;
+    }
+
+    /// Number of bytes of the current message received so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the decoder is waiting for the start of a message.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[cfg(feature = "cobs")]
+impl<const N: usize> Default for CobsDecoder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
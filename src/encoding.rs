@@ -0,0 +1,239 @@
+//! Encodings which can be applied to the payload before framing it.
+//!
+//! With the default [Encoding::Raw] the payload can contain any byte, including ones
+//! equal to the start and end indices, so a reader which lost track of the frame
+//! boundaries can mistake a payload byte for the start of a new frame.
+//! The other encodings trade a few bytes of overhead for payloads which never contain them.
+//! The length and crc bytes are still sent as is, so they can be equal to an index:
+//! a reader resyncing on them ([UcPack::resync](crate::UcPack::resync)) relies on the
+//! end index and crc checks to reject such candidates.
+
+use crate::{buffer::WriteBuffer, UcPackError};
+
+/// How the payload is encoded inside the frame. See the [module](self) docs.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// The payload is sent as is, compatible with the other ucpack implementations.
+    #[default]
+    Raw,
+    /// The payload is [COBS](https://en.wikipedia.org/wiki/Consistent_Overhead_Byte_Stuffing)
+    /// encoded, so it never contains a `0x00` byte, which is then used as the end index.
+    ///
+    /// The length byte and crc refer to the encoded payload, which is one byte bigger
+    /// than the raw one (every 254 bytes).
+    Cobs,
+    /// Payload bytes equal to the start index, the end index or `escape` are replaced
+    /// by `escape` followed by the byte XORed with [ESCAPE_XOR], so that the
+    /// payload never contains any of the indices.
+    ///
+    /// The length byte and crc refer to the escaped payload, which is bigger than the raw one
+    /// by one byte for every escaped byte. `escape` must differ from both indices,
+    /// and none of them should be equal to another one XORed with [ESCAPE_XOR].
+    ///
+    /// Only the payload is escaped: the length and crc bytes are written as is, so a
+    /// 65 bytes long payload (`0x41`) or a crc of `0x41` still puts a `b'A'` start index
+    /// inside the frame. See the [module](self) docs.
+    Escaped { escape: u8 },
+}
+
+/// Mask applied to the bytes following the escape byte of [Encoding::Escaped]
+pub const ESCAPE_XOR: u8 = 0x20;
+
+impl Encoding {
+    /// The end index actually used by frames carrying an encoded payload.
+    pub(crate) const fn end_index(self, end_index: u8) -> u8 {
+        match self {
+            Encoding::Raw | Encoding::Escaped { .. } => end_index,
+            Encoding::Cobs => 0x00,
+        }
+    }
+
+    /// Encodes `payload` into `out`, given the frame's start and end `indices`.
+    pub(crate) fn encode(
+        self,
+        payload: &[u8],
+        indices: [u8; 2],
+        out: &mut impl WriteBuffer,
+    ) -> Result<(), UcPackError> {
+        match self {
+            Encoding::Raw => out.push_slice(payload),
+            Encoding::Cobs => cobs_encode(payload, out),
+            Encoding::Escaped { escape } => {
+                for &byte in payload {
+                    match byte == escape || indices.contains(&byte) {
+                        true => out.push_slice(&[escape, byte ^ ESCAPE_XOR])?,
+                        false => out.push_u8(byte)?,
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Decodes `payload` into `out`, returning the decoded length.
+    pub(crate) fn decode(self, payload: &[u8], out: &mut [u8]) -> Result<usize, UcPackError> {
+        match self {
+            Encoding::Raw => {
+                out.get_mut(..payload.len())
+                    .ok_or(UcPackError::TooLong)?
+                    .copy_from_slice(payload);
+                Ok(payload.len())
+            }
+            Encoding::Cobs => cobs_decode(payload, out),
+            Encoding::Escaped { escape } => {
+                let mut bytes = payload.iter();
+                let mut written = 0;
+
+                while let Some(&byte) = bytes.next() {
+                    let byte = match byte == escape {
+                        true => bytes.next().ok_or(UcPackError::InvalidData)? ^ ESCAPE_XOR,
+                        false => byte,
+                    };
+
+                    *out.get_mut(written).ok_or(UcPackError::TooLong)? = byte;
+                    written += 1;
+                }
+
+                Ok(written)
+            }
+        }
+    }
+}
+
+/// Longest run of non-zero bytes a single COBS block can hold
+const COBS_BLOCK: usize = 254;
+
+fn cobs_encode(mut data: &[u8], out: &mut impl WriteBuffer) -> Result<(), UcPackError> {
+    loop {
+        let block = &data[..data.len().min(COBS_BLOCK)];
+
+        match block.iter().position(|&byte| byte == 0) {
+            // the zero is implied by the block's code
+            Some(len) => {
+                out.push_u8(len as u8 + 1)?;
+                out.push_slice(&block[..len])?;
+                data = &data[len + 1..];
+            }
+            // a full block isn't followed by an implied zero
+            None if block.len() == COBS_BLOCK => {
+                out.push_u8(COBS_BLOCK as u8 + 1)?;
+                out.push_slice(block)?;
+                data = &data[COBS_BLOCK..];
+
+                if data.is_empty() {
+                    return Ok(());
+                }
+            }
+            None => {
+                out.push_u8(block.len() as u8 + 1)?;
+                return out.push_slice(block);
+            }
+        }
+    }
+}
+
+fn cobs_decode(data: &[u8], out: &mut [u8]) -> Result<usize, UcPackError> {
+    let mut read = 0;
+    let mut written = 0;
+
+    while read < data.len() {
+        let code = usize::from(data[read]);
+        let block = data
+            .get(read + 1..read + code)
+            .ok_or(UcPackError::InvalidData)?;
+
+        if block.contains(&0) {
+            return Err(UcPackError::InvalidData);
+        }
+
+        out.get_mut(written..written + block.len())
+            .ok_or(UcPackError::TooLong)?
+            .copy_from_slice(block);
+
+        read += code;
+        written += block.len();
+
+        if code != COBS_BLOCK + 1 && read < data.len() {
+            *out.get_mut(written).ok_or(UcPackError::TooLong)? = 0;
+            written += 1;
+        }
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod test {
+    use super::Encoding;
+    use crate::buffer::SliceCursor;
+
+    fn roundtrip_with(encoding: Encoding, raw: &[u8], encoded: &[u8]) {
+        let mut buffer = [0u8; 512];
+        let mut cursor = SliceCursor::from_slice(&mut buffer[..]);
+        encoding.encode(raw, [b'A', b'#'], &mut cursor).unwrap();
+        assert_eq!(&cursor.inner()[..cursor.index()], encoded);
+
+        let mut decoded = [0u8; 512];
+        let len = encoding.decode(encoded, &mut decoded).unwrap();
+        assert_eq!(&decoded[..len], raw);
+    }
+
+    fn roundtrip(raw: &[u8], encoded: &[u8]) {
+        roundtrip_with(Encoding::Cobs, raw, encoded)
+    }
+
+    #[test]
+    fn cobs() {
+        roundtrip(&[], &[0x01]);
+        roundtrip(&[0x00], &[0x01, 0x01]);
+        roundtrip(&[0x00, 0x00], &[0x01, 0x01, 0x01]);
+        roundtrip(&[0x00, 0x11, 0x00], &[0x01, 0x02, 0x11, 0x01]);
+        roundtrip(&[0x11, 0x22, 0x00, 0x33], &[0x03, 0x11, 0x22, 0x02, 0x33]);
+        roundtrip(&[0x11, 0x00, 0x00, 0x00], &[0x02, 0x11, 0x01, 0x01, 0x01]);
+
+        // a full block (0x01..=0xFE) isn't followed by an implied zero
+        let mut data = [0u8; 255];
+        let mut encoded = [0u8; 257];
+        for i in 0..254 {
+            data[i] = i as u8 + 1;
+            encoded[i + 1] = i as u8 + 1;
+        }
+        encoded[0] = 0xFF;
+        roundtrip(&data[..254], &encoded[..255]);
+
+        // 0x01..=0xFF spans two blocks
+        data[254] = 0xFF;
+        encoded[255] = 0x02;
+        encoded[256] = 0xFF;
+        roundtrip(&data, &encoded);
+    }
+
+    #[test]
+    fn cobs_invalid() {
+        let mut decoded = [0u8; 16];
+
+        for encoded in [&[0x00][..], &[0x03, 0x11], &[0x03, 0x00, 0x11]] {
+            assert!(Encoding::Cobs.decode(encoded, &mut decoded).is_err());
+        }
+        assert!(Encoding::Cobs
+            .decode(&[0x02, 0x11, 0x02, 0x22], &mut decoded[..2])
+            .is_err());
+    }
+
+    #[test]
+    fn escaped() {
+        let encoding = Encoding::Escaped { escape: 0x7D };
+
+        roundtrip_with(encoding, &[], &[]);
+        roundtrip_with(encoding, &[1, 2, 3], &[1, 2, 3]);
+        roundtrip_with(
+            encoding,
+            &[b'A', 1, b'#', 0x7D, 0x5D],
+            &[0x7D, b'a', 1, 0x7D, 0x03, 0x7D, 0x5D, 0x5D],
+        );
+
+        let mut decoded = [0u8; 16];
+        assert!(encoding.decode(&[1, 0x7D], &mut decoded).is_err());
+    }
+}
@@ -0,0 +1,228 @@
+//! Fragmentation of payloads too big for a single frame, see [UcPack::serialize_fragmented].
+//!
+//! Each fragment is a regular frame, whose payload starts with a 3 bytes header:
+//! the id of the message, the index of the fragment and the total number of fragments.
+//! The rest of the payload is the next chunk of the serialized message.
+
+use crate::{
+    buffer::WriteBuffer,
+    checksum::{Checksum, Crc8},
+    encoding::Encoding,
+    UcPack, UcPackError,
+};
+
+/// Size of the header preceding the data of every fragment
+pub const HEADER_SIZE: usize = 3;
+
+/// Fragment payloads are limited to the size of classic frames,
+/// even with [UcPack::with_extended_length], so they can be built on the stack
+const MAX_FRAGMENT_PAYLOAD: usize = u8::MAX as usize;
+
+/// Fits a whole fragment frame with any reasonably sized checksum
+const FRAME_SCRATCH_SIZE: usize = 2 * MAX_FRAGMENT_PAYLOAD;
+
+/// Number of message bytes carried by every fragment (but the last one)
+pub(crate) const fn data_size(encoding: Encoding) -> usize {
+    match encoding {
+        Encoding::Raw => MAX_FRAGMENT_PAYLOAD - HEADER_SIZE,
+        // leaves room for the payload to double in size once encoded
+        _ => MAX_FRAGMENT_PAYLOAD / 2 - HEADER_SIZE,
+    }
+}
+
+/// Splits the bytes written into it into fragments, handing each one's frame to `emit`
+pub(crate) struct Fragmenter<'a, C, F> {
+    ucpack: &'a UcPack<C>,
+    /// Header and data of the fragment being filled
+    fragment: [u8; MAX_FRAGMENT_PAYLOAD],
+    len: usize,
+    written: usize,
+    emit: F,
+}
+
+impl<'a, C: Checksum, F: FnMut(&[u8])> Fragmenter<'a, C, F> {
+    pub fn new(ucpack: &'a UcPack<C>, id: u8, total: u8, emit: F) -> Self {
+        let mut fragment = [0; MAX_FRAGMENT_PAYLOAD];
+        fragment[..HEADER_SIZE].copy_from_slice(&[id, 0, total]);
+
+        Self {
+            ucpack,
+            fragment,
+            len: HEADER_SIZE,
+            written: 0,
+            emit,
+        }
+    }
+
+    /// Emits the fragment being filled, starting the next one
+    pub fn flush(&mut self) -> Result<(), UcPackError> {
+        let mut frame = [0u8; FRAME_SCRATCH_SIZE];
+        let n = self
+            .ucpack
+            .frame_raw(&self.fragment[..self.len], &mut frame)?;
+        (self.emit)(&frame[..n]);
+
+        self.fragment[1] = self.fragment[1].wrapping_add(1);
+        self.len = HEADER_SIZE;
+        Ok(())
+    }
+}
+
+impl<C: Checksum, F: FnMut(&[u8])> WriteBuffer for Fragmenter<'_, C, F> {
+    fn push_slice(&mut self, mut bf: &[u8]) -> Result<(), UcPackError> {
+        let data_size = HEADER_SIZE + data_size(self.ucpack.encoding);
+
+        while !bf.is_empty() {
+            if self.len == data_size {
+                self.flush()?;
+            }
+
+            let n = bf.len().min(data_size - self.len);
+            self.fragment[self.len..self.len + n].copy_from_slice(&bf[..n]);
+            self.len += n;
+            self.written += n;
+            bf = &bf[n..];
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn bytes_written(&self) -> usize {
+        self.written
+    }
+}
+
+/// Reassembles the payload of messages split by [UcPack::serialize_fragmented],
+/// using a fixed buffer of `MAX` bytes.
+///
+/// Fragments have to be pushed in order. The first fragment of a message always
+/// starts reassembling it, dropping any incomplete message (see [Reassembler::is_empty]).
+/// The complete payload can then be deserialized with [UcPack::deserialize_payload]:
+/// ```rust
+/// use ucpack::{fragment::Reassembler, UcPack};
+///
+/// let ucpack = UcPack::default();
+/// let mut reassembler = Reassembler::<1024>::new(ucpack);
+///
+/// let blob = [[7u8; 32]; 16];
+/// let mut frames = Vec::new();
+/// ucpack
+///     .serialize_fragmented(1, &blob, |frame| frames.push(frame.to_vec()))
+///     .unwrap();
+/// assert_eq!(frames.len(), 3);
+///
+/// assert!(reassembler.push(&frames[0]).unwrap().is_none());
+/// assert!(reassembler.push(&frames[1]).unwrap().is_none());
+/// let payload = reassembler.push(&frames[2]).unwrap().unwrap();
+/// assert_eq!(ucpack.deserialize_payload::<[[u8; 32]; 16]>(payload).unwrap(), blob);
+/// ```
+pub struct Reassembler<const MAX: usize, C = Crc8> {
+    ucpack: UcPack<C>,
+    buffer: [u8; MAX],
+    len: usize,
+    /// Id of the message being reassembled, and its total number of fragments
+    message: Option<(u8, u8)>,
+    next_index: u8,
+}
+
+impl<const MAX: usize, C: Checksum> Reassembler<MAX, C> {
+    pub const fn new(ucpack: UcPack<C>) -> Self {
+        Self {
+            ucpack,
+            buffer: [0; MAX],
+            len: 0,
+            message: None,
+            next_index: 0,
+        }
+    }
+
+    /// Verifies a fragment frame and appends its data to the message being reassembled.
+    ///
+    /// Returns the whole payload once its last fragment is pushed, or `None` while
+    /// fragments are still missing. Fails with:
+    /// - any error [UcPack::validate_frame] returns, if the frame itself is faulty
+    /// - [UcPackError::WrongFragment] if a fragment has been skipped or belongs to
+    ///   another message, dropping the partial message
+    /// - [UcPackError::InvalidData] if the fragment header is malformed
+    /// - [UcPackError::BufferFull] if the message doesn't fit in `MAX` bytes
+    pub fn push(&mut self, frame: &[u8]) -> Result<Option<&[u8]>, UcPackError> {
+        let payload = self.ucpack.validate_frame(frame)?;
+
+        let mut decoded = [0u8; u8::MAX as usize];
+        let fragment = match self.ucpack.encoding {
+            Encoding::Raw => payload,
+            encoding => {
+                let len = encoding.decode(payload, &mut decoded)?;
+                &decoded[..len]
+            }
+        };
+
+        let Some((&[id, index, total], data)) = fragment.split_first_chunk() else {
+            return Err(UcPackError::InvalidData);
+        };
+        if index >= total {
+            return Err(UcPackError::InvalidData);
+        }
+
+        if index == 0 {
+            self.reset();
+            self.message = Some((id, total));
+        } else {
+            let expected = match self.message {
+                Some(message) if message == (id, total) => self.next_index,
+                _ => 0,
+            };
+            if index != expected {
+                self.reset();
+                return Err(UcPackError::WrongFragment {
+                    expected,
+                    found: index,
+                });
+            }
+        }
+
+        self.append(data)?;
+        self.next_index = index + 1;
+        if self.next_index < total {
+            return Ok(None);
+        }
+
+        self.message = None;
+        Ok(Some(&self.buffer[..self.len]))
+    }
+
+    /// Discards the message being reassembled.
+    pub fn reset(&mut self) {
+        self.len = 0;
+        self.message = None;
+        self.next_index = 0;
+    }
+
+    /// Number of payload bytes reassembled so far.
+    pub fn len(&self) -> usize {
+        match self.message {
+            Some(_) => self.len,
+            None => 0,
+        }
+    }
+
+    /// Whether the reassembler is waiting for the first fragment of a message.
+    pub fn is_empty(&self) -> bool {
+        self.message.is_none()
+    }
+
+    fn append(&mut self, data: &[u8]) -> Result<(), UcPackError> {
+        let free = MAX - self.len;
+        if data.len() > free {
+            self.reset();
+            return Err(UcPackError::BufferFull {
+                needed: data.len() - free,
+            });
+        }
+
+        self.buffer[self.len..self.len + data.len()].copy_from_slice(data);
+        self.len += data.len();
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+//! Helpers to fuzz the deserialization of ucpack frames, e.g. with `cargo fuzz`.
+
+use alloc::vec::Vec;
+
+use arbitrary::{Arbitrary, Unstructured};
+
+use crate::UcPack;
+
+/// A frame of the default [UcPack] configuration wrapping a random payload.
+///
+/// Start index, length and end index are always right, while the crc is
+/// deliberately wrong about half of the time, so that fuzzed inputs make it
+/// past the header checks and into the deserializer instead of being
+/// rejected straight away.
+/// ```rust
+/// use arbitrary::{Arbitrary, Unstructured};
+/// use ucpack::{fuzz::RawFrame, UcPack};
+///
+/// let ucpack = UcPack::default();
+///
+/// // a 3 bytes long payload, followed by `false` for a correct crc
+/// let mut data = Unstructured::new(&[3, 1, 2, 3, 0]);
+/// let RawFrame(frame) = RawFrame::arbitrary(&mut data).unwrap();
+///
+/// assert_eq!(frame, ucpack.frame_raw_vec(&[1, 2, 3]).unwrap());
+/// assert!(ucpack.verify(&frame).is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct RawFrame(pub Vec<u8>);
+
+impl<'a> Arbitrary<'a> for RawFrame {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let len = u.int_in_range(0..=UcPack::MAX_PAYLOAD)?;
+        let payload = u.bytes(len.min(u.len()))?;
+
+        let mut frame = UcPack::default()
+            .frame_raw_vec(payload)
+            .expect("payload can't be longer than MAX_PAYLOAD");
+
+        if u.arbitrary::<bool>()? {
+            let crc = frame.last_mut().expect("frames are never empty");
+            *crc ^= u.int_in_range(1..=u8::MAX)?;
+        }
+
+        Ok(Self(frame))
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (1, Some(1 + UcPack::MAX_PAYLOAD + 2))
+    }
+}
+
+impl AsRef<[u8]> for RawFrame {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
@@ -0,0 +1,144 @@
+use core::marker::PhantomData;
+
+use serde::Deserialize;
+
+use crate::{
+    checksum::{Checksum, Crc8},
+    UcPack, UcPackError,
+};
+
+/// Iterator over the packets contained in a contiguous buffer,
+/// for example a DMA buffer filled with multiple back-to-back frames.
+///
+/// Yields every complete packet found in the buffer after verifying its
+/// indices and crc, so it can be passed straight to [UcPack::deserialize_slice].
+/// Packets failing verification are yielded as errors and skipped over.
+///
+/// Iteration stops as soon as the remaining data doesn't contain a complete packet.
+pub struct PacketIter<'a, C = Crc8> {
+    ucpack: &'a UcPack<C>,
+    buffer: &'a [u8],
+}
+
+impl<'a, C: Checksum> PacketIter<'a, C> {
+    pub(crate) fn new(ucpack: &'a UcPack<C>, buffer: &'a [u8]) -> Self {
+        Self { ucpack, buffer }
+    }
+
+    /// The data which hasn't been consumed by the iterator yet,
+    /// such as an incomplete trailing packet.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.buffer
+    }
+}
+
+impl<'a, C: Checksum> Iterator for PacketIter<'a, C> {
+    type Item = Result<&'a [u8], UcPackError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let packet = self.ucpack.complete_frame(self.buffer)?;
+        self.buffer = &self.buffer[packet.len()..];
+
+        Some(self.ucpack.check_packet(packet).map(|_| packet))
+    }
+}
+
+/// Iterator over the valid frames contained in a buffer, such as a drained DMA buffer.
+///
+/// Unlike [PacketIter], frames don't have to be back-to-back: any garbage between
+/// them is skipped, along with corrupted frames. Iteration stops once there are no
+/// more valid frames, keeping any incomplete trailing frame for the next read
+/// (see [FrameIter::consumed] and [FrameIter::remaining]).
+pub struct FrameIter<'a, C = Crc8> {
+    ucpack: &'a UcPack<C>,
+    buffer: &'a [u8],
+    consumed: usize,
+}
+
+impl<'a, C: Checksum> FrameIter<'a, C> {
+    pub(crate) fn new(ucpack: &'a UcPack<C>, buffer: &'a [u8]) -> Self {
+        Self {
+            ucpack,
+            buffer,
+            consumed: 0,
+        }
+    }
+
+    /// Number of bytes of the buffer consumed so far, which can be discarded:
+    /// yielded frames and the garbage surrounding them.
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// The data which hasn't been consumed by the iterator yet,
+    /// such as an incomplete trailing frame.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.buffer
+    }
+
+    fn advance(&mut self, n: usize) {
+        self.buffer = &self.buffer[n..];
+        self.consumed += n;
+    }
+}
+
+impl<'a, C: Checksum> Iterator for FrameIter<'a, C> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut incomplete = None;
+
+        for offset in 0..self.buffer.len() {
+            match self.ucpack.check_message(&self.buffer[offset..]) {
+                Ok(frame) => {
+                    self.advance(offset + frame.len());
+                    return Some(frame);
+                }
+                // might be a frame which hasn't fully arrived yet, unless
+                // a valid frame follows
+                Err(UcPackError::Eof { .. }) => {
+                    incomplete.get_or_insert(offset);
+                }
+                Err(_) => {}
+            }
+        }
+
+        self.advance(incomplete.unwrap_or(self.buffer.len()));
+        None
+    }
+}
+
+/// Iterator deserializing every message contained in a contiguous buffer.
+///
+/// Works just like [PacketIter], but yields the deserialized `T`
+/// instead of the raw packets.
+pub struct MessageIter<'a, T, C = Crc8> {
+    packets: PacketIter<'a, C>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T, C: Checksum> MessageIter<'a, T, C> {
+    pub(crate) fn new(ucpack: &'a UcPack<C>, buffer: &'a [u8]) -> Self {
+        Self {
+            packets: PacketIter::new(ucpack, buffer),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The data which hasn't been consumed by the iterator yet,
+    /// such as an incomplete trailing message.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.packets.remaining()
+    }
+}
+
+impl<'a, T: Deserialize<'a>, C: Checksum> Iterator for MessageIter<'a, T, C> {
+    type Item = Result<T, UcPackError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ucpack = self.packets.ucpack;
+        let packet = self.packets.next()?;
+
+        Some(packet.and_then(|packet| ucpack.deserialize_slice(packet)))
+    }
+}
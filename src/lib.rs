@@ -1,49 +1,151 @@
 #![doc = include_str!("../README.md")]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "async")]
+pub mod asynch;
 pub mod buffer;
+pub mod builder;
+pub mod byte_array;
+pub mod checksum;
+#[cfg(feature = "tokio")]
+pub mod codec;
 pub mod de;
+pub mod decoder;
+pub mod encoding;
+pub mod fragment;
+#[cfg(feature = "arbitrary")]
+pub mod fuzz;
+pub mod iter;
 mod macros;
+#[cfg(target_has_atomic = "8")]
+pub mod pool;
 pub mod ser;
+pub mod size;
+pub mod tag;
+#[cfg(feature = "proptest")]
+pub mod testing;
+pub mod time;
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 use core::fmt::Display;
 
-use buffer::{SliceCursor, WriteBuffer};
-use serde::Deserialize;
+pub use buffer::SliceCursor;
+pub use builder::UcPackBuilder;
+
+use buffer::{CountingBuffer, ReadBuffer, ScratchCursor, WriteBuffer};
+use checksum::{Checksum, Crc8, Crc8Config, CrcWriter};
+use encoding::Encoding;
+use iter::{FrameIter, MessageIter, PacketIter};
+use serde::{de::IgnoredAny, Deserialize};
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, string::String, string::ToString, vec::Vec};
 
 #[derive(Debug)]
 /// Error returned by the ucpack crate
+///
+/// With the `alloc` feature, [UcPackError::Eof] and [UcPackError::InvalidData] errors
+/// raised while deserializing a payload are wrapped in [UcPackError::AtPosition].
+/// As cargo unifies features, any other crate of the build can enable it: match
+/// on [UcPackError::inner] rather than on the error itself to handle both cases.
+/// ```rust
+/// use ucpack::{UcPack, UcPackError};
+///
+/// let ucpack = UcPack::default();
+/// let mut buffer = [0u8; 8];
+/// let n = ucpack.serialize_slice(&1u8, &mut buffer).unwrap();
+///
+/// let err = ucpack.deserialize_slice::<u16>(&buffer[..n]).unwrap_err();
+/// assert!(matches!(err.inner(), UcPackError::Eof { .. }));
+/// ```
 pub enum UcPackError {
-    /// Tried to serialize a variant index bigger than `255`.
+    /// Tried to serialize a variant index bigger than `255`
+    /// (or `65535` with [UcPack::with_wide_variants]).
     BadVariant,
+    /// Received a variant index which doesn't correspond to any variant of the enum.
+    UnknownVariant { index: u16 },
     /// The cursor does not have any more data to deserialize from.
-    Eof,
+    ///
+    /// `needed` is the number of additional bytes which were required, or a lower bound
+    /// of it (at least 1) when the data source can't tell.
+    Eof { needed: usize },
     /// Serialization / Deserialization of this type is not supported by the ucpack protocol.
     /// If you think this is a mistake, please open an issue.
     NoSupport(&'static str),
-    /// Tried to serialize more than 256 bytes of payload data. This is a restriction
-    /// imposed by the protocol.
+    /// The payload exceeds the maximum frame length: 255 bytes, or 65535 with
+    /// [UcPack::with_extended_length]. This is a restriction imposed by the protocol.
+    ///
+    /// Also returned for strings, byte arrays and tagged sequences longer than 255
+    /// elements, as their length is encoded as a single byte.
     TooLong,
     /// Tried to serialize more bytes than the buffer could possible handle.
-    BufferFull,
+    ///
+    /// `needed` is the number of additional bytes the buffer would have had to fit.
+    /// When returned by [UcPack::serialize_slice] it accounts for the whole frame.
+    ///
+    /// Also returned when deserializing a value which doesn't fit a fixed capacity
+    /// container, such as a `heapless::String<N>`. The capacity isn't known to the
+    /// deserializer, so `needed` is the length of the rejected value instead.
+    BufferFull { needed: usize },
     /// There was a serde error during serialization.
-    #[cfg(not(feature = "std"))]
+    #[cfg(not(feature = "alloc"))]
     SerError,
-    #[cfg(feature = "std")]
+    #[cfg(feature = "alloc")]
     SerError(String),
     /// There was a serde error during deserialization.
-    #[cfg(not(feature = "std"))]
+    #[cfg(not(feature = "alloc"))]
     DeError,
-    #[cfg(feature = "std")]
+    #[cfg(feature = "alloc")]
     DeError(String),
+    /// Tried to deserialize a type which requires a self-describing format, such as
+    /// internally tagged or untagged enums, from the untagged format. Use externally
+    /// tagged enums (the default) instead, or enable the tagged format.
+    ///
+    /// Adjacently tagged enums are supported as long as their variants are either
+    /// newtype or tuple variants.
+    NotSelfDescribing,
     /// Input data for deserialization has problems finding a representation in a given data format
     ///
     /// For example: a serialized boolean value ∉ {0, 1}
     InvalidData,
+    /// Received a command with a different code than the expected one.
+    WrongCode { expected: u8, found: u8 },
     /// Received a message with a wrong/faulty crc. Probably indicates data corruption.
-    WrongCrc,
-    /// Received a message containing wrong index/indices for the start and stop bytes.
-    WrongIndex,
+    ///
+    /// `expected` is the checksum computed over the received payload, `found` the one
+    /// the message ended with, read as big-endian integers (only their last 4 bytes
+    /// for wider checksums).
+    WrongCrc { expected: u32, found: u32 },
+    /// Received a fragment out of order, see [fragment::Reassembler]: either the
+    /// `expected` one went missing or `found` belongs to another message.
+    WrongFragment { expected: u8, found: u8 },
+    /// Received a message containing a wrong start or stop index.
+    WrongIndex {
+        expected: u8,
+        found: u8,
+        position: IndexKind,
+    },
+    /// The payload of a message wasn't entirely consumed by deserialization,
+    /// see [UcPack::with_deny_trailing_bytes].
+    TrailingBytes { remaining: usize },
+    /// An I/O error occurred while reading or writing a message.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+    /// The payload of a message ended early ([UcPackError::Eof]) or contained
+    /// [UcPackError::InvalidData].
+    ///
+    /// `position` is the number of payload bytes which had been read when the error
+    /// occurred: where the missing value starts, or right after the invalid one.
+    ///
+    /// Only available with the `alloc` feature, use [UcPackError::inner] and
+    /// [UcPackError::position] to inspect errors regardless of it.
+    #[cfg(feature = "alloc")]
+    AtPosition {
+        position: usize,
+        error: Box<UcPackError>,
+    },
 }
 
 impl Display for UcPackError {
@@ -52,48 +154,242 @@ impl Display for UcPackError {
             Self::NoSupport(typename) => {
                 return write!(f, "there's no support for type {typename}")
             }
-            Self::Eof => "not enough data to deserialize",
+            Self::UnknownVariant { index } => {
+                return write!(f, "unknown enum variant index {index}");
+            }
+            Self::NotSelfDescribing => {
+                "internally tagged and untagged enums are not supported by the untagged format, \
+                 use externally tagged enums or enable the tagged format"
+            }
+            Self::Eof { needed } => {
+                return write!(
+                    f,
+                    "not enough data to deserialize ({needed} more bytes needed)"
+                );
+            }
             Self::InvalidData => "invalid data for data type",
-            Self::BadVariant => "tried to serialize a variant index bigger than 255",
-            Self::TooLong => "tried to serialize more than 256 bytes",
-            Self::BufferFull => "tried to write but buffer reached capacity",
+            Self::BadVariant => "tried to serialize a variant index which doesn't fit its width",
+            Self::TooLong => "payload exceeds the maximum frame length",
+            Self::BufferFull { needed } => {
+                return write!(
+                    f,
+                    "tried to write but buffer reached capacity ({needed} more bytes needed)"
+                );
+            }
 
-            Self::WrongCrc => "crc verification failed",
-            Self::WrongIndex => "invalid start and/or stop indices",
+            Self::WrongCode { expected, found } => {
+                return write!(
+                    f,
+                    "expected command code {expected:#04x}, found {found:#04x}"
+                );
+            }
+            Self::WrongCrc { expected, found } => {
+                return write!(
+                    f,
+                    "crc verification failed (expected {expected:#04x}, found {found:#04x})"
+                );
+            }
+            Self::WrongFragment { expected, found } => {
+                return write!(f, "expected fragment {expected}, found {found}");
+            }
+            Self::WrongIndex {
+                expected,
+                found,
+                position,
+            } => {
+                return write!(
+                    f,
+                    "expected {position} index {expected:#04x}, found {found:#04x}"
+                );
+            }
+            Self::TrailingBytes { remaining } => {
+                return write!(f, "{remaining} bytes left over after deserializing");
+            }
 
-            #[cfg(not(feature = "std"))]
+            #[cfg(not(feature = "alloc"))]
             Self::SerError => "serde encountered an error serializing",
-            #[cfg(feature = "std")]
+            #[cfg(feature = "alloc")]
             Self::SerError(err) => {
                 return write!(f, "serde encountered an error while serializing: {err}");
             }
 
-            #[cfg(not(feature = "std"))]
+            #[cfg(not(feature = "alloc"))]
             Self::DeError => "serde encountered an error deserializing",
-            #[cfg(feature = "std")]
+            #[cfg(feature = "alloc")]
             Self::DeError(err) => {
                 return write!(f, "serde encountered an error while deserializing: {err}");
             }
+
+            #[cfg(feature = "std")]
+            Self::Io(err) => return write!(f, "i/o error: {err}"),
+            #[cfg(feature = "alloc")]
+            Self::AtPosition { position, error } => {
+                return write!(f, "{error} at payload byte {position}");
+            }
         };
 
         f.write_str(msg)
     }
 }
 
+/// Which of the two indices delimiting a frame a [UcPackError::WrongIndex] refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum IndexKind {
+    Start,
+    End,
+}
+
+impl Display for IndexKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::Start => "start",
+            Self::End => "end",
+        })
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for UcPackError {
+    fn format(&self, f: defmt::Formatter) {
+        match self {
+            Self::NoSupport(typename) => {
+                defmt::write!(f, "there's no support for type {=str}", typename)
+            }
+            Self::UnknownVariant { index } => {
+                defmt::write!(f, "unknown enum variant index {=u16}", index)
+            }
+            Self::NotSelfDescribing => defmt::write!(
+                f,
+                "internally tagged and untagged enums are not supported by the untagged format"
+            ),
+            Self::Eof { needed } => defmt::write!(
+                f,
+                "not enough data to deserialize ({=usize} more bytes needed)",
+                needed
+            ),
+            Self::InvalidData => defmt::write!(f, "invalid data for data type"),
+            Self::BadVariant => defmt::write!(
+                f,
+                "tried to serialize a variant index which doesn't fit its width"
+            ),
+            Self::TooLong => defmt::write!(f, "payload exceeds the maximum frame length"),
+            Self::BufferFull { needed } => defmt::write!(
+                f,
+                "tried to write but buffer reached capacity ({=usize} more bytes needed)",
+                needed
+            ),
+            Self::WrongCode { expected, found } => defmt::write!(
+                f,
+                "expected command code {=u8:#04x}, found {=u8:#04x}",
+                expected,
+                found
+            ),
+            Self::WrongCrc { expected, found } => defmt::write!(
+                f,
+                "crc verification failed (expected {=u32:#04x}, found {=u32:#04x})",
+                expected,
+                found
+            ),
+            Self::WrongFragment { expected, found } => {
+                defmt::write!(f, "expected fragment {=u8}, found {=u8}", expected, found)
+            }
+            Self::WrongIndex {
+                expected,
+                found,
+                position,
+            } => defmt::write!(
+                f,
+                "expected {} index {=u8:#04x}, found {=u8:#04x}",
+                position,
+                expected,
+                found
+            ),
+            Self::TrailingBytes { remaining } => {
+                defmt::write!(f, "{=usize} bytes left over after deserializing", remaining)
+            }
+
+            #[cfg(not(feature = "alloc"))]
+            Self::SerError => defmt::write!(f, "serde encountered an error serializing"),
+            #[cfg(not(feature = "alloc"))]
+            Self::DeError => defmt::write!(f, "serde encountered an error deserializing"),
+
+            // variants holding data defmt can't encode by itself
+            #[cfg(feature = "alloc")]
+            err => defmt::write!(f, "{}", defmt::Display2Format(err)),
+        }
+    }
+}
+
 #[cfg(feature = "std")]
-impl std::error::Error for UcPackError {}
+impl std::error::Error for UcPackError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::AtPosition { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl UcPackError {
+    /// Byte of the payload the error occurred at, if known.
+    ///
+    /// Always `None` without the `alloc` feature.
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            #[cfg(feature = "alloc")]
+            Self::AtPosition { position, .. } => Some(*position),
+            _ => None,
+        }
+    }
+
+    /// The error itself, without the position it occurred at.
+    ///
+    /// Match on this rather than on the error directly, so that the same code
+    /// works whether or not [UcPackError::AtPosition] is enabled.
+    pub fn inner(&self) -> &Self {
+        match self {
+            #[cfg(feature = "alloc")]
+            Self::AtPosition { error, .. } => error,
+            err => err,
+        }
+    }
+
+    /// Attaches the `position` within the payload to end of data and invalid data errors
+    fn at(self, _position: usize) -> Self {
+        match self {
+            #[cfg(feature = "alloc")]
+            Self::Eof { .. } | Self::InvalidData => Self::AtPosition {
+                position: _position,
+                error: Box::new(self),
+            },
+            err => err,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for UcPackError {
+    fn from(err: std::io::Error) -> Self {
+        match err.kind() {
+            std::io::ErrorKind::UnexpectedEof => Self::Eof { needed: 1 },
+            _ => Self::Io(err),
+        }
+    }
+}
 
 impl serde::ser::Error for UcPackError {
     fn custom<T>(_msg: T) -> Self
     where
         T: Display,
     {
-        #[cfg(not(feature = "std"))]
+        #[cfg(not(feature = "alloc"))]
         {
             Self::SerError
         }
 
-        #[cfg(feature = "std")]
+        #[cfg(feature = "alloc")]
         {
             Self::SerError(_msg.to_string())
         }
@@ -105,23 +401,48 @@ impl serde::de::Error for UcPackError {
     where
         T: Display,
     {
-        #[cfg(not(feature = "std"))]
+        #[cfg(not(feature = "alloc"))]
         {
             Self::DeError
         }
 
-        #[cfg(feature = "std")]
+        #[cfg(feature = "alloc")]
         {
             Self::DeError(_msg.to_string())
         }
     }
+
+    /// Values which have been read successfully, but aren't valid for their
+    /// type (such as a zero for `NonZeroU16`).
+    fn invalid_value(_unexp: serde::de::Unexpected, _exp: &dyn serde::de::Expected) -> Self {
+        Self::InvalidData
+    }
+
+    /// Values whose length the type rejects, reported as [UcPackError::BufferFull]
+    /// with the rejected length as `needed`. Called by fixed capacity containers
+    /// (`heapless::String<N>`, ...) for values which are too long, and by
+    /// [byte_array] for arrays which don't have exactly `N` bytes.
+    fn invalid_length(len: usize, _exp: &dyn serde::de::Expected) -> Self {
+        Self::BufferFull { needed: len }
+    }
 }
 // impl core for UcPackError {}
 
 /// UcPack structure
-pub struct UcPack {
+///
+/// Frames are protected by a [Crc8] by default, see [UcPack::with_checksum] to use another [Checksum].
+#[derive(Debug, Clone, Copy)]
+pub struct UcPack<C = Crc8> {
     start_index: u8,
     end_index: u8,
+    tagged: bool,
+    wide_variants: bool,
+    encoding: Encoding,
+    extended_length: bool,
+    strict: bool,
+    deny_trailing_bytes: bool,
+    crc_covers_header: bool,
+    checksum: C,
 }
 
 impl Default for UcPack {
@@ -135,25 +456,218 @@ impl UcPack {
         Self {
             start_index,
             end_index,
+            tagged: false,
+            wide_variants: false,
+            encoding: Encoding::Raw,
+            extended_length: false,
+            strict: cfg!(feature = "strict"),
+            deny_trailing_bytes: false,
+            crc_covers_header: false,
+            checksum: Crc8::new(),
         }
     }
 
-    #[cfg(feature = "std")]
+    /// Configures a [UcPack] through an [UcPackBuilder].
+    pub const fn builder() -> UcPackBuilder {
+        UcPackBuilder::new()
+    }
+
+    /// Number of bytes the default configuration adds around the payload:
+    /// start index, length, end index and crc8.
+    /// See [UcPack::overhead] for other configurations.
+    pub const FRAME_OVERHEAD: usize = 4;
+
+    /// Longest payload the default configuration can carry,
+    /// see [UcPack::max_payload_size] for other configurations.
+    pub const MAX_PAYLOAD: usize = u8::MAX as usize;
+
+    /// Size of the biggest frame with the default configuration,
+    /// see [UcPack::max_frame_size] for other configurations.
+    pub const MAX_FRAME_SIZE: usize = Self::MAX_PAYLOAD + Self::FRAME_OVERHEAD;
+}
+
+impl<C: Checksum> UcPack<C> {
+    /// Enables or disables the tagged flavor of the format, where every value is
+    /// preceded by its [type tag](crate::tag), making payloads self-describing.
+    ///
+    /// This allows deserializing types relying on `deserialize_any`, such as
+    /// `#[serde(untagged)]` enums, at the cost of a bigger payload.
+    /// Tagged payloads are NOT compatible with the untagged (default) format.
+    pub const fn with_tagged(self, tagged: bool) -> Self {
+        Self { tagged, ..self }
+    }
+
+    /// Encodes enum variant indices as little-endian `u16`s instead of a single byte,
+    /// allowing enums with more than 256 variants.
+    ///
+    /// Payloads containing enums are NOT compatible between the two widths.
+    pub const fn with_wide_variants(self, wide_variants: bool) -> Self {
+        Self {
+            wide_variants,
+            ..self
+        }
+    }
+
+    /// Sets how payloads are encoded inside the frame, see [Encoding].
+    ///
+    /// Encoded payloads are NOT compatible with the other ucpack implementations.
+    ///
+    /// # Panics
+    /// If the escape byte of [Encoding::Escaped] is equal to the start or end index,
+    /// at compile time when called in a const context.
+    pub const fn with_encoding(self, encoding: Encoding) -> Self {
+        if let Encoding::Escaped { escape } = encoding {
+            assert!(
+                escape != self.start_index && escape != self.end_index,
+                "the escape byte must differ from the start and end indices"
+            );
+        }
+
+        Self { encoding, ..self }
+    }
+
+    /// Encodes the payload length as a little-endian `u16` instead of a single byte,
+    /// allowing payloads up to 65535 bytes long. Frames are one byte bigger.
+    ///
+    /// Extended frames are NOT compatible with classic ones: consider using
+    /// a different start index for them, so that the two can't be confused.
+    /// Payloads using an [Encoding] other than [Encoding::Raw] are still limited
+    /// to 255 bytes before being encoded.
+    pub const fn with_extended_length(self, extended_length: bool) -> Self {
+        Self {
+            extended_length,
+            ..self
+        }
+    }
+
+    /// Enables or disables the verification of the start and end indices of received frames,
+    /// useful to talk to devices using nonstandard ones.
+    ///
+    /// Defaults to whether the `strict` feature is enabled.
+    pub const fn with_strict(self, strict: bool) -> Self {
+        Self { strict, ..self }
+    }
+
+    /// Rejects messages whose payload isn't entirely consumed by deserialization
+    /// with [UcPackError::TrailingBytes], catching schema mismatches early.
+    ///
+    /// Disabled by default, as newer versions of a message may append optional fields
+    /// (see [de::Deserializer::remaining_bytes]).
+    pub const fn with_deny_trailing_bytes(self, deny_trailing_bytes: bool) -> Self {
+        Self {
+            deny_trailing_bytes,
+            ..self
+        }
+    }
+
+    /// Computes the checksum over the header (start index and length) of frames as well,
+    /// instead of their payload only, so that a corrupted length is always detected.
+    ///
+    /// Otherwise a corrupted length makes the receiver verify the checksum against
+    /// the wrong bytes, which is only caught as long as they don't happen to match it.
+    /// With [UcPack::serialize_cobs_slice] it covers the length bytes.
+    ///
+    /// Frames covering their header are NOT compatible with the other ucpack implementations.
+    pub const fn with_crc_covers_header(self, crc_covers_header: bool) -> Self {
+        Self {
+            crc_covers_header,
+            ..self
+        }
+    }
+
+    /// Sets the checksum appended to every frame, see [Checksum].
+    ///
+    /// Frames using a checksum other than [Crc8] are NOT compatible
+    /// with the other ucpack implementations.
+    pub const fn with_checksum<D: Checksum>(self, checksum: D) -> UcPack<D> {
+        UcPack {
+            start_index: self.start_index,
+            end_index: self.end_index,
+            tagged: self.tagged,
+            wide_variants: self.wide_variants,
+            encoding: self.encoding,
+            extended_length: self.extended_length,
+            strict: self.strict,
+            deny_trailing_bytes: self.deny_trailing_bytes,
+            crc_covers_header: self.crc_covers_header,
+            checksum,
+        }
+    }
+
+    #[cfg(feature = "alloc")]
     pub fn serialize_vec(
         &self,
         payload: &impl serde::ser::Serialize,
     ) -> Result<Vec<u8>, UcPackError> {
-        let mut buffer = vec![self.start_index, 0];
+        let mut buffer = Vec::new();
+        self.serialize_into(payload, &mut buffer)?;
+        Ok(buffer)
+    }
+
+    /// Same as [UcPack::serialize_vec], but reuses the allocation of `buffer`,
+    /// which is cleared beforehand. It's left empty if serialization fails.
+    #[cfg(feature = "alloc")]
+    pub fn serialize_into(
+        &self,
+        payload: &impl serde::ser::Serialize,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), UcPackError> {
+        buffer.clear();
+        self.append_frame(payload, buffer)
+            .inspect_err(|_| buffer.clear())
+    }
+
+    #[cfg(feature = "alloc")]
+    fn append_frame(
+        &self,
+        payload: &impl serde::ser::Serialize,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), UcPackError> {
+        self.append_frame_with(buffer, |writer| self.write_payload(payload, writer))
+    }
+
+    /// Appends a frame to `buffer`, whose payload is written by `write`.
+    #[cfg(feature = "alloc")]
+    fn append_frame_with(
+        &self,
+        buffer: &mut Vec<u8>,
+        write: impl FnOnce(&mut CrcWriter<&mut Vec<u8>, C>) -> Result<(), UcPackError>,
+    ) -> Result<(), UcPackError> {
+        let header_size = self.header_size();
+        buffer.extend_from_slice(&[self.start_index, 0, 0][..header_size]);
 
-        let mut serializer = ser::Serializer::new(&mut buffer);
-        payload.serialize(&mut serializer)?;
+        let mut writer = CrcWriter::new(&mut *buffer, self.checksum);
+        write(&mut writer)?;
+        let checksum = writer.finalize();
 
         let data_end = buffer.len();
-        buffer[1] = u8::try_from(data_end - 2).map_err(|_| UcPackError::TooLong)?;
+        self.write_length(buffer, data_end - header_size)?;
+        let checksum = self.frame_checksum(&buffer[..data_end], checksum);
+
+        buffer.push(self.end_index());
+        buffer.extend_from_slice(checksum.as_ref());
 
-        buffer.push(self.end_index);
-        buffer.push(crc8_slice(&buffer[2..data_end]));
+        Ok(())
+    }
+
+    /// Serializes `payload` into a fixed capacity `heapless::Vec`, returning
+    /// [UcPackError::BufferFull] if the frame doesn't fit in `N` bytes.
+    /// ```rust
+    /// # use ucpack::UcPack;
+    /// let frame = UcPack::default().serialize_heapless::<8>(&(1u8, 2u16)).unwrap();
+    /// assert_eq!(frame.len(), 7);
+    /// ```
+    #[cfg(feature = "heapless")]
+    pub fn serialize_heapless<const N: usize>(
+        &self,
+        payload: &impl serde::ser::Serialize,
+    ) -> Result<heapless::Vec<u8, N>, UcPackError> {
+        let mut buffer = heapless::Vec::new();
+        // the capacity is exactly N, so this can't fail
+        let _ = buffer.resize_default(N);
 
+        let len = self.serialize_slice(payload, &mut buffer)?;
+        buffer.truncate(len);
         Ok(buffer)
     }
 
@@ -162,79 +676,1184 @@ impl UcPack {
         payload: &impl serde::ser::Serialize,
         buffer: &mut [u8],
     ) -> Result<usize, UcPackError> {
+        let capacity = buffer.len();
+
+        self.write_slice(payload, buffer).map_err(|err| match err {
+            // serialization stops at the first write which doesn't fit,
+            // so compute how much is actually missing for the whole frame
+            UcPackError::BufferFull { .. } => match self.serialize_size(payload) {
+                Ok(size) => UcPackError::BufferFull {
+                    needed: size.saturating_sub(capacity),
+                },
+                Err(err) => err,
+            },
+            err => err,
+        })
+    }
+
+    fn write_slice(
+        &self,
+        payload: &impl serde::ser::Serialize,
+        buffer: &mut [u8],
+    ) -> Result<usize, UcPackError> {
+        self.write_slice_with(buffer, |writer| self.write_payload(payload, writer))
+    }
+
+    /// Writes a frame into `buffer`, whose payload is written by `write`,
+    /// returning its size.
+    fn write_slice_with(
+        &self,
+        buffer: &mut [u8],
+        write: impl FnOnce(&mut CrcWriter<&mut SliceCursor<&mut [u8]>, C>) -> Result<(), UcPackError>,
+    ) -> Result<usize, UcPackError> {
+        let header_size = self.header_size();
+
         let mut cursor = SliceCursor::from_slice(&mut *buffer);
-        cursor.push_slice(&[self.start_index, 0])?; // start_index + placeholder for length
+        // start_index + placeholder for length
+        cursor.push_slice(&[self.start_index, 0, 0][..header_size])?;
 
-        let mut serializer = ser::Serializer::new(&mut cursor);
-        payload.serialize(&mut serializer)?;
+        let mut writer = CrcWriter::new(&mut cursor, self.checksum);
+        write(&mut writer)?;
+        let checksum = writer.finalize();
 
         let data_end = cursor.index();
-        let crc = crc8_slice(&cursor.inner()[2..data_end]);
-        cursor.push_slice(&[self.end_index, crc])?;
+        cursor.push_u8(self.end_index())?;
+        cursor.push_slice(checksum.as_ref())?;
 
         let total_size = cursor.index();
 
-        buffer[1] = u8::try_from(data_end - 2).map_err(|_| UcPackError::TooLong)?;
+        self.write_length(buffer, data_end - header_size)?;
+        let checksum = self.frame_checksum(&buffer[..data_end], checksum);
+        buffer[data_end + 1..total_size].copy_from_slice(checksum.as_ref());
         Ok(total_size)
     }
 
+    /// Wraps `payload`, already serialized elsewhere, in a frame written into `buffer`,
+    /// returning its size.
+    ///
+    /// The frame is the same [UcPack::serialize_slice] would produce for a value
+    /// serializing into `payload`, which is encoded as configured. Returns
+    /// [UcPackError::TooLong] if `payload` doesn't fit in a frame.
+    /// ```rust
+    /// # use ucpack::UcPack;
+    /// let ucpack = UcPack::default();
+    /// let mut frame = [0u8; 8];
+    /// let n = ucpack.frame_raw(&[1, 2, 0], &mut frame).unwrap();
+    ///
+    /// let mut serialized = [0u8; 8];
+    /// let m = ucpack.serialize_slice(&(1u8, 2u16), &mut serialized).unwrap();
+    /// assert_eq!(frame[..n], serialized[..m]);
+    /// ```
+    pub fn frame_raw(&self, payload: &[u8], buffer: &mut [u8]) -> Result<usize, UcPackError> {
+        let size = self.raw_frame_size(payload)?;
+        if size > buffer.len() {
+            return Err(UcPackError::BufferFull {
+                needed: size - buffer.len(),
+            });
+        }
+
+        self.write_slice_with(buffer, |writer| self.encode_payload(payload, writer))
+    }
+
+    /// Same as [UcPack::frame_raw], but into a newly allocated `Vec`.
+    #[cfg(feature = "alloc")]
+    pub fn frame_raw_vec(&self, payload: &[u8]) -> Result<Vec<u8>, UcPackError> {
+        let mut buffer = Vec::with_capacity(self.raw_frame_size(payload)?);
+        self.append_frame_with(&mut buffer, |writer| self.encode_payload(payload, writer))?;
+        Ok(buffer)
+    }
+
+    /// Serializes `payload` into as many frames as it takes, for payloads too big for a
+    /// single one, handing each frame to `emit` as soon as it's complete.
+    /// Returns the number of frames.
+    ///
+    /// Every frame carries a fragment of the payload along with the message `id`, see the
+    /// [fragment] module, and is put back together by a [fragment::Reassembler].
+    /// Payloads can span up to 255 fragments of 252 bytes each, or 124 with an [Encoding].
+    /// ```rust
+    /// # use ucpack::UcPack;
+    /// let mut frames = Vec::new();
+    /// let n = UcPack::default()
+    ///     .serialize_fragmented(1, &[[0u16; 32]; 8], |frame| frames.push(frame.to_vec()))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(n, 3);
+    /// assert_eq!(frames.len(), 3);
+    /// ```
+    #[doc(alias = "fragment", alias = "chunk")]
+    pub fn serialize_fragmented(
+        &self,
+        id: u8,
+        payload: &impl serde::ser::Serialize,
+        emit: impl FnMut(&[u8]),
+    ) -> Result<usize, UcPackError> {
+        let mut counter = CountingBuffer::default();
+        payload.serialize(&mut self.serializer(&mut counter))?;
+
+        let total = counter
+            .count()
+            .div_ceil(fragment::data_size(self.encoding))
+            .max(1);
+        let total = u8::try_from(total).map_err(|_| UcPackError::TooLong)?;
+
+        let mut fragmenter = fragment::Fragmenter::new(self, id, total, emit);
+        payload.serialize(&mut self.serializer(&mut fragmenter))?;
+        fragmenter.flush()?;
+
+        Ok(total.into())
+    }
+
+    /// Size of the frame wrapping the already serialized `payload`
+    fn raw_frame_size(&self, payload: &[u8]) -> Result<usize, UcPackError> {
+        let mut counter = CountingBuffer::default();
+        self.encode_payload(payload, &mut counter)?;
+
+        let length = counter.count();
+        if length > self.max_payload_size() {
+            return Err(UcPackError::TooLong);
+        }
+
+        Ok(self.frame_size(length))
+    }
+
+    /// Serializes `payload` into `buffer` without any framing: no indexes, length
+    /// nor checksum. Returns the number of bytes written.
+    ///
+    /// Meant for payloads carried by another envelope which already frames them,
+    /// or to use ucpack as a plain serde format. The output is the payload section
+    /// of the frame [UcPack::serialize_slice] would produce. The [Encoding]
+    /// isn't applied though, as there are no indexes to escape.
+    /// ```rust
+    /// # use ucpack::UcPack;
+    /// let ucpack = UcPack::default();
+    /// let mut buffer = [0u8; 3];
+    /// let n = ucpack.serialize_payload(&(1u8, 2u16), &mut buffer).unwrap();
+    /// assert_eq!(buffer[..n], [1, 2, 0]);
+    /// ```
+    pub fn serialize_payload(
+        &self,
+        payload: &impl serde::ser::Serialize,
+        buffer: &mut [u8],
+    ) -> Result<usize, UcPackError> {
+        let capacity = buffer.len();
+        let mut cursor = SliceCursor::from_slice(buffer);
+
+        match payload.serialize(&mut self.serializer(&mut cursor)) {
+            Ok(()) => Ok(cursor.index()),
+            Err(UcPackError::BufferFull { .. }) => {
+                let mut counter = CountingBuffer::default();
+                payload.serialize(&mut self.serializer(&mut counter))?;
+                Err(UcPackError::BufferFull {
+                    needed: counter.count().saturating_sub(capacity),
+                })
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Serializes `payload` into a COBS framed message, returning its size.
+    ///
+    /// Instead of being surrounded by the start and end indices, the length, payload and
+    /// checksum are [COBS](https://en.wikipedia.org/wiki/Consistent_Overhead_Byte_Stuffing)
+    /// encoded, so that they never contain a `0x00`, and followed by a `0x00` delimiter.
+    /// Unlike [Encoding::Cobs], which only encodes the payload of a classic frame,
+    /// the result can be split by any tool which understands COBS framing.
+    ///
+    /// The configured [Encoding] isn't applied. The message is built on the stack,
+    /// so extended frames are limited to about 500 bytes of payload.
+    /// ```rust
+    /// # use ucpack::UcPack;
+    /// let ucpack = UcPack::default();
+    /// let mut buffer = [0u8; 16];
+    /// let n = ucpack.serialize_cobs_slice(&(0u8, 2u16), &mut buffer).unwrap();
+    /// assert_eq!(buffer[n - 1], 0);
+    /// assert!(!buffer[..n - 1].contains(&0));
+    ///
+    /// let value: (u8, u16) = ucpack.deserialize_cobs_slice(&buffer[..n]).unwrap();
+    /// assert_eq!(value, (0, 2));
+    /// ```
+    #[cfg(feature = "cobs")]
+    pub fn serialize_cobs_slice(
+        &self,
+        payload: &impl serde::ser::Serialize,
+        buffer: &mut [u8],
+    ) -> Result<usize, UcPackError> {
+        let mut cursor = SliceCursor::from_slice(buffer);
+        self.write_cobs(payload, &mut cursor)?;
+        Ok(cursor.index())
+    }
+
+    /// Same as [UcPack::serialize_cobs_slice], but into a newly allocated `Vec`.
+    #[cfg(all(feature = "cobs", feature = "alloc"))]
+    pub fn serialize_cobs_vec(
+        &self,
+        payload: &impl serde::ser::Serialize,
+    ) -> Result<Vec<u8>, UcPackError> {
+        let mut buffer = Vec::new();
+        self.write_cobs(payload, &mut buffer)?;
+        Ok(buffer)
+    }
+
+    #[cfg(feature = "cobs")]
+    fn write_cobs<B: WriteBuffer>(
+        &self,
+        payload: &impl serde::ser::Serialize,
+        buffer: &mut B,
+    ) -> Result<(), UcPackError> {
+        let length_size = self.header_size() - 1;
+
+        let mut block = [0u8; COBS_SCRATCH_SIZE];
+        let mut cursor = SliceCursor::from_slice(&mut block[..]);
+        // placeholder for the length
+        cursor.push_slice(&[0, 0][..length_size])?;
+
+        // running out of scratch space means the message is too long
+        let too_long = |err| match err {
+            UcPackError::BufferFull { .. } => UcPackError::TooLong,
+            err => err,
+        };
+
+        let mut writer = CrcWriter::new(&mut cursor, self.checksum);
+        payload
+            .serialize(&mut self.serializer(&mut writer))
+            .map_err(too_long)?;
+        let checksum = writer.finalize();
+
+        let data_end = cursor.index();
+        cursor.push_slice(checksum.as_ref()).map_err(too_long)?;
+        let block_size = cursor.index();
+
+        let length = data_end - length_size;
+        if length > self.max_payload_size() {
+            return Err(UcPackError::TooLong);
+        }
+        block[..length_size].copy_from_slice(&length.to_le_bytes()[..length_size]);
+
+        let checksum = self.frame_checksum(&block[..data_end], checksum);
+        block[data_end..block_size].copy_from_slice(checksum.as_ref());
+
+        Encoding::Cobs.encode(&block[..block_size], [0, 0], buffer)?;
+        buffer.push_u8(0)
+    }
+
+    /// Serializes `payload` and writes the whole frame to `writer`, flushing it afterwards.
+    ///
+    /// The frame is serialized on the stack (or on the heap, for extended frames
+    /// and checksums bigger than 4 bytes) first, so nothing is written to `writer`
+    /// if serialization fails.
+    #[cfg(feature = "std")]
+    pub fn write_to<W: std::io::Write>(
+        &self,
+        payload: &impl serde::ser::Serialize,
+        writer: &mut W,
+    ) -> Result<(), UcPackError> {
+        if self.extended_length || C::SIZE > 4 {
+            writer.write_all(&self.serialize_vec(payload)?)?;
+        } else {
+            let mut buffer = [0u8; u8::MAX as usize + 7];
+            let n = self.serialize_slice(payload, &mut buffer)?;
+            writer.write_all(&buffer[..n])?;
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Computes the size of the whole frame (start index, length, payload,
+    /// end index and checksum) `payload` would be serialized into, without
+    /// writing it anywhere.
+    ///
+    /// Returns [UcPackError::TooLong] if the payload doesn't fit in a single frame.
+    pub fn serialize_size(
+        &self,
+        payload: &impl serde::ser::Serialize,
+    ) -> Result<usize, UcPackError> {
+        let mut counter = CountingBuffer::default();
+        self.write_payload(payload, &mut counter)?;
+
+        let length = counter.count();
+        if length > self.max_payload_size() {
+            return Err(UcPackError::TooLong);
+        }
+
+        Ok(self.frame_size(length))
+    }
+
+    /// Deserializes the message at the beginning of `buffer`.
+    ///
+    /// Borrowed data (`&str`, `&[u8]`, `Cow<str>` marked as `#[serde(borrow)]`, ...)
+    /// isn't copied, but rather points straight into `buffer`:
+    /// ```rust
+    /// # use ucpack::UcPack;
+    /// #[derive(serde::Serialize, serde::Deserialize)]
+    /// struct Msg<'a> {
+    ///     id: u8,
+    ///     name: &'a str,
+    /// }
+    ///
+    /// let ucpack = UcPack::default();
+    /// let mut buffer = [0u8; 16];
+    /// ucpack.serialize_slice(&Msg { id: 1, name: "alvik" }, &mut buffer).unwrap();
+    ///
+    /// let msg: Msg<'_> = ucpack.deserialize_slice(&buffer).unwrap();
+    /// assert_eq!(msg.name, "alvik");
+    /// ```
+    ///
+    /// Which means the message can't outlive `buffer`:
+    /// ```compile_fail
+    /// # use ucpack::UcPack;
+    /// # #[derive(serde::Serialize, serde::Deserialize)]
+    /// # struct Msg<'a> {
+    /// #     id: u8,
+    /// #     name: &'a str,
+    /// # }
+    /// let ucpack = UcPack::default();
+    /// let msg: Msg<'_> = {
+    ///     let mut buffer = [0u8; 16];
+    ///     ucpack.serialize_slice(&Msg { id: 1, name: "alvik" }, &mut buffer).unwrap();
+    ///     ucpack.deserialize_slice(&buffer).unwrap()
+    /// };
+    /// assert_eq!(msg.name, "alvik");
+    /// ```
+    ///
+    /// Nor can `buffer` be overwritten while the message is alive:
+    /// ```compile_fail
+    /// # use ucpack::UcPack;
+    /// let ucpack = UcPack::default();
+    /// let mut buffer = [0u8; 16];
+    /// ucpack.serialize_slice(&(1u8, "alvik"), &mut buffer).unwrap();
+    ///
+    /// let (_, name): (u8, &str) = ucpack.deserialize_slice(&buffer).unwrap();
+    /// ucpack.serialize_slice(&(2u8, "other"), &mut buffer).unwrap();
+    /// assert_eq!(name, "alvik");
+    /// ```
     pub fn deserialize_slice<'d, 'b, T>(&self, buffer: &'b [u8]) -> Result<T, UcPackError>
     where
         T: Deserialize<'d>,
         'b: 'd,
     {
-        let packet = is_complete_message(buffer).ok_or(UcPackError::Eof)?;
-        let [index, _, payload @ .., end_index, crc] = packet else {
-            return Err(UcPackError::Eof);
+        self.deserialize_slice_consume(buffer)
+            .map(|(value, _)| value)
+    }
+
+    /// Same as [UcPack::deserialize_slice], but also returns the number of bytes
+    /// the message occupied at the beginning of `buffer` (its payload length + 4,
+    /// with the default configuration),
+    /// which is where the next message starts.
+    #[doc(
+        alias = "deserialize_slice_taken",
+        alias = "deserialize_slice_with_len"
+    )]
+    pub fn deserialize_slice_consume<'d, 'b, T>(
+        &self,
+        buffer: &'b [u8],
+    ) -> Result<(T, usize), UcPackError>
+    where
+        T: Deserialize<'d>,
+        'b: 'd,
+    {
+        self.deserialize_frame(buffer, true)
+    }
+
+    /// Deserializes the COBS framed message (see [UcPack::serialize_cobs_slice])
+    /// at the beginning of `buffer`, up to its `0x00` delimiter.
+    ///
+    /// The message is decoded on the stack, so borrowed data (`&str`, ...) isn't supported.
+    /// Returns [UcPackError::InvalidData] if the decoded length doesn't match
+    /// the payload's one.
+    #[cfg(feature = "cobs")]
+    pub fn deserialize_cobs_slice<'d, T>(&self, buffer: &[u8]) -> Result<T, UcPackError>
+    where
+        T: Deserialize<'d>,
+    {
+        let end = buffer
+            .iter()
+            .position(|&byte| byte == 0)
+            .ok_or(UcPackError::Eof { needed: 1 })?;
+
+        let mut block = [0u8; COBS_SCRATCH_SIZE];
+        let len = Encoding::Cobs.decode(&buffer[..end], &mut block)?;
+        let payload = self.cobs_payload(&block[..len])?;
+
+        self.read_payload(ScratchCursor::from_slice(payload))
+    }
+
+    /// Verifies the length and checksum of a decoded COBS `block`, returning its payload.
+    #[cfg(feature = "cobs")]
+    fn cobs_payload<'a>(&self, block: &'a [u8]) -> Result<&'a [u8], UcPackError> {
+        let length_size = self.header_size() - 1;
+        if block.len() < length_size + C::SIZE {
+            return Err(UcPackError::InvalidData);
+        }
+
+        let (length, rest) = block.split_at(length_size);
+        let length = length
+            .iter()
+            .rev()
+            .fold(0, |length, &byte| length << 8 | usize::from(byte));
+        if rest.len() != length + C::SIZE {
+            return Err(UcPackError::InvalidData);
+        }
+
+        let (payload, checksum) = rest.split_at(length);
+        let expected = self.checksum(self.checksummed(&block[..length_size + length], length_size));
+        if checksum != expected.as_ref() {
+            return Err(UcPackError::WrongCrc {
+                expected: checksum_value(expected.as_ref()),
+                found: checksum_value(checksum),
+            });
+        }
+
+        Ok(payload)
+    }
+
+    /// Same as [UcPack::deserialize_slice], but neither the checksum nor the indexes
+    /// of the frame are verified.
+    ///
+    /// Useful when the data comes from a link which already guarantees its integrity,
+    /// or to replay captured frames whose checksum has been tampered with.
+    /// The frame must still be complete, checksum bytes included.
+    pub fn deserialize_slice_unchecked<'d, 'b, T>(&self, buffer: &'b [u8]) -> Result<T, UcPackError>
+    where
+        T: Deserialize<'d>,
+        'b: 'd,
+    {
+        self.deserialize_frame(buffer, false)
+            .map(|(value, _)| value)
+    }
+
+    /// Deserializes a payload written by [UcPack::serialize_payload], which
+    /// takes up the whole `buffer`.
+    ///
+    /// Bytes left over after the value are ignored, unless
+    /// [UcPack::with_deny_trailing_bytes] is set.
+    pub fn deserialize_payload<'d, 'b, T>(&self, buffer: &'b [u8]) -> Result<T, UcPackError>
+    where
+        T: Deserialize<'d>,
+        'b: 'd,
+    {
+        self.read_payload(SliceCursor::from_slice(buffer))
+    }
+
+    fn deserialize_frame<'d, 'b, T>(
+        &self,
+        buffer: &'b [u8],
+        verify: bool,
+    ) -> Result<(T, usize), UcPackError>
+    where
+        T: Deserialize<'d>,
+        'b: 'd,
+    {
+        let payload = self.frame_payload(buffer, verify)?;
+        let frame_len = self.frame_size(payload.len());
+
+        let value = match self.encoding {
+            Encoding::Raw => self.read_payload(SliceCursor::from_slice(payload)),
+            // the decoded payload only lives on the stack, so it can't be borrowed from
+            encoding => {
+                let mut decoded = [0u8; u8::MAX as usize];
+                let len = encoding.decode(payload, &mut decoded)?;
+                self.read_payload(ScratchCursor::from_slice(&decoded[..len]))
+            }
         };
 
-        if cfg!(feature = "strict") && (*index != self.start_index || *end_index != self.end_index)
+        value.map(|value| (value, frame_len))
+    }
+
+    /// Verifies the frame at the beginning of `buffer` just like [UcPack::deserialize_slice]
+    /// does, returning its payload without deserializing it.
+    ///
+    /// Useful to forward payloads somewhere else. They're returned as they appear
+    /// in the frame: still encoded, if an [Encoding] is configured.
+    #[doc(alias = "payload", alias = "extract_payload")]
+    pub fn validate_frame<'a>(&self, buffer: &'a [u8]) -> Result<&'a [u8], UcPackError> {
+        self.frame_payload(buffer, true)
+    }
+
+    /// Verifies the frame at the beginning of `buffer` without deserializing it,
+    /// e.g. to acknowledge it before processing.
+    ///
+    /// Returns:
+    /// - [UcPackError::Eof] if the frame isn't complete yet
+    /// - [UcPackError::WrongIndex] if its indexes are wrong (only in strict mode)
+    /// - [UcPackError::WrongCrc] if it's been corrupted
+    pub fn verify(&self, buffer: &[u8]) -> Result<(), UcPackError> {
+        self.validate_frame(buffer).map(|_| ())
+    }
+
+    /// Returns the payload of the frame at the beginning of `buffer`,
+    /// verifying it if `verify` is set.
+    fn frame_payload<'a>(&self, buffer: &'a [u8], verify: bool) -> Result<&'a [u8], UcPackError> {
+        let packet = self
+            .complete_frame(buffer)
+            .ok_or_else(|| self.eof(buffer))?;
+        self.split_packet(packet, verify)
+    }
+
+    /// Reads a single message from `reader` and deserializes it.
+    ///
+    /// Exactly one frame is consumed from the reader: the start index and length
+    /// are read first, then the rest of the frame as indicated by the length.
+    /// Returns [UcPackError::Eof] if the reader ends before the frame is complete.
+    #[cfg(feature = "std")]
+    pub fn read_from<R, T>(&self, reader: &mut R) -> Result<T, UcPackError>
+    where
+        R: std::io::Read,
+        T: serde::de::DeserializeOwned,
+    {
+        let header_size = self.header_size();
+        let mut packet = vec![0u8; header_size];
+        reader.read_exact(&mut packet)?;
+
+        let total_size = self
+            .buffered_frame_size(&packet)
+            .ok_or_else(|| self.eof(&packet))?;
+        packet.resize(total_size, 0);
+        reader.read_exact(&mut packet[header_size..])?;
+
+        self.deserialize_slice(&packet)
+    }
+
+    /// Reads a single message from `buffer` and deserializes it, without requiring
+    /// the whole frame to be received beforehand: useful with streaming buffers such as
+    /// [EmbeddedReadAdapter](buffer::EmbeddedReadAdapter).
+    ///
+    /// Just like [UcPack::read_from](Self::read_from), the header is read first, then the
+    /// rest of the frame, into a stack buffer. Frames which don't fit in it (only possible
+    /// with extended frames and checksums bigger than 4 bytes) are rejected with
+    /// [UcPackError::TooLong] after reading their header.
+    pub fn deserialize_read<'de, B, T>(&self, mut buffer: B) -> Result<T, UcPackError>
+    where
+        B: ReadBuffer<'de>,
+        T: serde::de::DeserializeOwned,
+    {
+        let header_size = self.header_size();
+        let mut packet = [0u8; u8::MAX as usize + 7];
+        buffer.read_slice(&mut packet[..header_size])?;
+
+        let total_size = self
+            .buffered_frame_size(&packet)
+            .ok_or_else(|| self.eof(&packet))?;
+        let packet = packet.get_mut(..total_size).ok_or(UcPackError::TooLong)?;
+        buffer.read_slice(&mut packet[header_size..])?;
+
+        self.deserialize_slice(packet)
+    }
+
+    /// Serializes `payload` and pushes the whole frame into `writer`, returning its size.
+    ///
+    /// The payload is serialized once to compute its length, so nothing is pushed if
+    /// serialization fails. Then the header is pushed, followed by the payload, the end
+    /// index and the checksum.
+    ///
+    /// Payloads up to [ASYNC_CHUNK_SIZE] bytes (those of every classic frame) are then
+    /// serialized once more into a stack buffer. Bigger ones are serialized into the heap
+    /// with the `alloc` feature. Without it they're pushed in chunks of [ASYNC_CHUNK_SIZE]
+    /// bytes, serializing the payload again for each one: up to 256 passes for the
+    /// biggest extended frames, which is the price of not buffering them whole.
+    #[cfg(feature = "async")]
+    pub async fn serialize_async(
+        &self,
+        payload: &impl serde::ser::Serialize,
+        writer: &mut impl asynch::AsyncWriteBuffer,
+    ) -> Result<usize, UcPackError> {
+        let mut counter = CountingBuffer::default();
+        self.write_payload(payload, &mut counter)?;
+        let length = counter.count();
+
+        let header_size = self.header_size();
+        let mut header = [self.start_index, 0, 0];
+        self.write_length(&mut header, length)?;
+        let header = &header[..header_size];
+
+        let mut checksum = self.checksum;
+        if self.crc_covers_header {
+            checksum.update(header);
+        }
+        writer.push_slice(header).await?;
+
+        let mut sent = 0;
+        #[cfg(feature = "alloc")]
+        if length > ASYNC_CHUNK_SIZE {
+            let mut buffer = Vec::with_capacity(length);
+            self.write_payload(payload, &mut buffer)?;
+            checksum.update(&buffer);
+            writer.push_slice(&buffer).await?;
+            sent = length;
+        }
+
+        let mut chunk = [0u8; ASYNC_CHUNK_SIZE];
+        while sent < length {
+            let mut window = buffer::WindowBuffer::new(&mut chunk, sent);
+            match self.write_payload(payload, &mut window) {
+                Err(UcPackError::BufferFull { .. }) if window.is_full() => {}
+                result => result?,
+            }
+
+            let n = window.len();
+            checksum.update(&chunk[..n]);
+            writer.push_slice(&chunk[..n]).await?;
+            sent += n;
+        }
+
+        writer.push_u8(self.end_index()).await?;
+        writer.push_slice(checksum.finalize().as_ref()).await?;
+        Ok(self.frame_size(length))
+    }
+
+    /// Reads a single message from `reader` and deserializes it.
+    ///
+    /// The header is read first, then exactly the rest of the frame: the payload, the end
+    /// index and the checksum, which are verified before deserializing the payload.
+    /// Frames are read into a stack buffer fitting any classic frame. Bigger extended
+    /// frames are read into the heap with the `alloc` feature, and rejected with
+    /// [UcPackError::TooLong] after reading their header otherwise.
+    #[cfg(feature = "async")]
+    pub async fn deserialize_async<'de, T>(
+        &self,
+        reader: &mut impl asynch::AsyncReadBuffer<'de>,
+    ) -> Result<T, UcPackError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let header_size = self.header_size();
+        let mut packet = [0u8; u8::MAX as usize + 7];
+        reader.read_slice(&mut packet[..header_size]).await?;
+
+        let total_size = self
+            .buffered_frame_size(&packet)
+            .ok_or_else(|| self.eof(&packet))?;
+
+        if let Some(packet) = packet.get_mut(..total_size) {
+            reader.read_slice(&mut packet[header_size..]).await?;
+            return self.deserialize_slice(packet);
+        }
+
+        #[cfg(feature = "alloc")]
         {
-            return Err(UcPackError::WrongIndex);
+            let mut frame = alloc::vec![0u8; total_size];
+            frame[..header_size].copy_from_slice(&packet[..header_size]);
+            reader.read_slice(&mut frame[header_size..]).await?;
+            self.deserialize_slice(&frame)
         }
 
-        let expected_crc = crc8_slice(payload);
-        if expected_crc != *crc {
-            return Err(UcPackError::WrongCrc);
+        #[cfg(not(feature = "alloc"))]
+        Err(UcPackError::TooLong)
+    }
+
+    /// Returns an iterator over the complete packets found back-to-back in `buffer`.
+    ///
+    /// See [PacketIter] for more details.
+    pub fn packet_iter<'a>(&'a self, buffer: &'a [u8]) -> PacketIter<'a, C> {
+        PacketIter::new(self, buffer)
+    }
+
+    /// Returns an iterator over the valid frames found in `buffer`, skipping any garbage.
+    ///
+    /// See [FrameIter] for more details.
+    pub fn frames<'a>(&'a self, buffer: &'a [u8]) -> FrameIter<'a, C> {
+        FrameIter::new(self, buffer)
+    }
+
+    /// Returns an iterator deserializing every message found back-to-back in `buffer`.
+    ///
+    /// See [MessageIter] for more details.
+    pub fn iter_messages<'a, T>(&'a self, buffer: &'a [u8]) -> MessageIter<'a, T, C>
+    where
+        T: Deserialize<'a>,
+    {
+        MessageIter::new(self, buffer)
+    }
+
+    /// Serializes `payload` preceded by a single byte command `code`, into a [Vec].
+    ///
+    /// This mirrors the way the original ucPack firmware identifies its messages,
+    /// by an arbitrary code (usually an ASCII character such as `b'J'`) rather than
+    /// by the declaration order of an enum's variants.
+    /// See [UcPack::command_code] and [UcPack::deserialize_command] for the receiving end.
+    #[cfg(feature = "alloc")]
+    pub fn serialize_command_vec(
+        &self,
+        code: u8,
+        payload: &impl serde::ser::Serialize,
+    ) -> Result<Vec<u8>, UcPackError> {
+        self.serialize_vec(&(code, payload))
+    }
+
+    /// Serializes `payload` preceded by a single byte command `code`, into `buffer`.
+    ///
+    /// See [UcPack::serialize_command_vec] for more details.
+    pub fn serialize_command_slice(
+        &self,
+        code: u8,
+        payload: &impl serde::ser::Serialize,
+        buffer: &mut [u8],
+    ) -> Result<usize, UcPackError> {
+        self.serialize_slice(&(code, payload), buffer)
+    }
+
+    /// Verifies the message contained in `buffer` and returns its command code,
+    /// so that the receiver can dispatch it to the right type.
+    pub fn command_code(&self, buffer: &[u8]) -> Result<u8, UcPackError> {
+        // the rest of the payload is ignored, not consumed
+        let (code, _): (u8, IgnoredAny) = self
+            .with_deny_trailing_bytes(false)
+            .deserialize_slice(buffer)?;
+        Ok(code)
+    }
+
+    /// Deserializes a message serialized with [UcPack::serialize_command_vec]
+    /// (or [UcPack::serialize_command_slice]), checking its code is `expected_code`.
+    pub fn deserialize_command<'d, 'b, T>(
+        &self,
+        buffer: &'b [u8],
+        expected_code: u8,
+    ) -> Result<T, UcPackError>
+    where
+        T: Deserialize<'d>,
+        'b: 'd,
+    {
+        match self.deserialize_slice(buffer)? {
+            (code, payload) if code == expected_code => Ok(payload),
+            (code, _) => Err(UcPackError::WrongCode {
+                expected: expected_code,
+                found: code,
+            }),
         }
+    }
 
-        let mut cursor = SliceCursor::from_slice(payload);
-        let mut de = de::Deserializer::new(&mut cursor);
-        T::deserialize(&mut de)
+    /// Reads the header at the beginning of `buffer`, without waiting
+    /// for (or verifying) the rest of the frame.
+    ///
+    /// Returns [UcPackError::Eof] if the header hasn't been entirely received yet,
+    /// and [UcPackError::WrongIndex] if `buffer` doesn't begin with the start index.
+    /// ```rust
+    /// use ucpack::UcPack;
+    ///
+    /// let ucpack = UcPack::default();
+    /// let mut frame = [0u8; 16];
+    /// let n = ucpack.serialize_slice(&(1u8, 2u16), &mut frame).unwrap();
+    ///
+    /// let header = ucpack.peek_header(&frame[..2]).unwrap();
+    /// assert_eq!(header.payload_len, 3);
+    /// assert_eq!(header.total_len(), n);
+    /// ```
+    pub fn peek_header(&self, buffer: &[u8]) -> Result<FrameHeader, UcPackError> {
+        let header = buffer
+            .get(..self.header_size())
+            .ok_or_else(|| self.eof(buffer))?;
+        self.check_index(IndexKind::Start, header[0])?;
+
+        let overhead = self.overhead();
+        let total_len = self
+            .buffered_frame_size(header)
+            .ok_or_else(|| self.eof(buffer))?;
+        Ok(FrameHeader {
+            start: header[0],
+            payload_len: (total_len - overhead) as u16,
+            overhead,
+        })
+    }
+
+    /// Checks whether `buffer` begins with a complete message, just like the
+    /// free [is_complete_message] function, but also makes sure it starts
+    /// and ends with this configuration's indices.
+    ///
+    /// Returns:
+    /// - `Ok`: a slice containing the whole message (which hasn't been verified by its crc yet)
+    /// - `Err(UcPackError::Eof { .. })`: `buffer` begins like a message, but more data has yet to arrive
+    /// - `Err(UcPackError::WrongIndex { .. })`: `buffer` doesn't begin with a message,
+    ///   the reader has to resync (see [UcPack::resync])
+    pub fn is_complete_message<'a>(&self, buffer: &'a [u8]) -> Result<&'a [u8], UcPackError> {
+        if let Some(&index) = buffer.first() {
+            self.check_index(IndexKind::Start, index)?;
+        }
+
+        let message = self
+            .complete_frame(buffer)
+            .ok_or_else(|| self.eof(buffer))?;
+        self.check_index(IndexKind::End, message[message.len() - self.trailer_size()])?;
+        Ok(message)
+    }
+
+    /// Like [UcPack::is_complete_message], but the message's crc is verified too,
+    /// so that corrupted messages can be rejected before deserializing them.
+    ///
+    /// Returns [UcPackError::WrongCrc] if the message is complete but corrupted.
+    pub fn check_message<'a>(&self, buffer: &'a [u8]) -> Result<&'a [u8], UcPackError> {
+        let message = self.is_complete_message(buffer)?;
+        self.check_packet(message).map(|_| message)
+    }
+
+    /// Looks for the start of the next plausible frame in `buffer`, returning its offset.
+    ///
+    /// A frame is plausible when it starts with the start index, is complete and
+    /// both its end index and crc are valid. This allows recovering after a corrupted
+    /// or dropped byte desynchronized the reader, by discarding everything before
+    /// the returned offset.
+    ///
+    /// Returns `None` if there's no plausible frame in `buffer` (yet).
+    pub fn resync(&self, buffer: &[u8]) -> Option<usize> {
+        (0..buffer.len()).find(|&offset| self.check_message(&buffer[offset..]).is_ok())
+    }
+
+    /// Like [UcPack::resync], but also returns the plausible frame found at the offset,
+    /// which can be passed straight to [UcPack::deserialize_slice].
+    pub fn find_frame<'a>(&self, buffer: &'a [u8]) -> Option<(usize, &'a [u8])> {
+        let offset = self.resync(buffer)?;
+        self.complete_frame(&buffer[offset..])
+            .map(|frame| (offset, frame))
+    }
+
+    /// Serializes `payload` into `buffer`, encoding it as configured.
+    fn write_payload<B: WriteBuffer>(
+        &self,
+        payload: &impl serde::ser::Serialize,
+        buffer: &mut B,
+    ) -> Result<(), UcPackError> {
+        if self.encoding == Encoding::Raw {
+            return payload.serialize(&mut self.serializer(buffer));
+        }
+
+        let mut raw = [0u8; u8::MAX as usize];
+        let mut cursor = SliceCursor::from_slice(&mut raw[..]);
+        payload
+            .serialize(&mut self.serializer(&mut cursor))
+            .map_err(|err| match err {
+                UcPackError::BufferFull { .. } => UcPackError::TooLong,
+                err => err,
+            })?;
+
+        let len = cursor.index();
+        self.encode_payload(&raw[..len], buffer)
+    }
+
+    /// Writes the already serialized `payload` into `buffer`, encoding it as configured.
+    fn encode_payload<B: WriteBuffer>(
+        &self,
+        payload: &[u8],
+        buffer: &mut B,
+    ) -> Result<(), UcPackError> {
+        let indices = [self.start_index, self.end_index];
+        self.encoding.encode(payload, indices, buffer)
+    }
+
+    /// Size of the frame header: the start index and the length
+    const fn header_size(&self) -> usize {
+        match self.extended_length {
+            true => 3,
+            false => 2,
+        }
+    }
+
+    /// Longest payload a frame can carry: 255 bytes, or 65535 with
+    /// [UcPack::with_extended_length].
+    pub const fn max_payload_size(&self) -> usize {
+        match self.extended_length {
+            true => u16::MAX as usize,
+            false => u8::MAX as usize,
+        }
+    }
+
+    /// Size of the frame trailer: the end index and the checksum
+    const fn trailer_size(&self) -> usize {
+        1 + C::SIZE
+    }
+
+    /// Number of bytes frames add around their payload: start index, length,
+    /// end index and checksum, sized as configured.
+    pub const fn overhead(&self) -> usize {
+        self.header_size() + self.trailer_size()
+    }
+
+    /// Size of the frame carrying a payload of `payload_len` bytes.
+    /// ```rust
+    /// # use ucpack::{checksum::Crc16, UcPack};
+    /// let ucpack = UcPack::default().with_checksum(Crc16::new());
+    /// let mut frame = [0u8; 16];
+    /// let n = ucpack.serialize_slice(&(1u8, 2u16), &mut frame).unwrap();
+    /// assert_eq!(n, ucpack.frame_size(3));
+    /// ```
+    pub const fn frame_size(&self, payload_len: usize) -> usize {
+        payload_len + self.overhead()
+    }
+
+    /// Size of the biggest frame, carrying a payload of [UcPack::max_payload_size] bytes.
+    pub const fn max_frame_size(&self) -> usize {
+        self.frame_size(self.max_payload_size())
+    }
+
+    fn checksum(&self, payload: &[u8]) -> C::Output {
+        let mut checksum = self.checksum;
+        checksum.update(payload);
+        checksum.finalize()
+    }
+
+    /// The bytes of `frame`, made of a `header_size` bytes header followed by
+    /// the payload, covered by the checksum
+    fn checksummed<'a>(&self, frame: &'a [u8], header_size: usize) -> &'a [u8] {
+        match self.crc_covers_header {
+            true => frame,
+            false => &frame[header_size..],
+        }
+    }
+
+    /// The checksum of `frame` (its header followed by its payload), given
+    /// the `streamed` one computed while writing the payload alone
+    fn frame_checksum(&self, frame: &[u8], streamed: C::Output) -> C::Output {
+        match self.crc_covers_header {
+            true => self.checksum(frame),
+            false => streamed,
+        }
+    }
+
+    /// Size of the frame whose beginning is held by `buffer`, see the [frame_size] function
+    fn buffered_frame_size(&self, buffer: &[u8]) -> Option<usize> {
+        frame_size(buffer, self.extended_length, C::SIZE)
+    }
+
+    /// The error for `buffer` holding the beginning of a frame which isn't complete yet
+    fn eof(&self, buffer: &[u8]) -> UcPackError {
+        let size = self
+            .buffered_frame_size(buffer)
+            .unwrap_or(self.header_size());
+        UcPackError::Eof {
+            needed: size.saturating_sub(buffer.len()).max(1),
+        }
+    }
+
+    /// Checks the index `found` at `position` is the configured one
+    fn check_index(&self, position: IndexKind, found: u8) -> Result<(), UcPackError> {
+        let expected = match position {
+            IndexKind::Start => self.start_index,
+            IndexKind::End => self.end_index(),
+        };
+
+        match found == expected {
+            true => Ok(()),
+            false => Err(UcPackError::WrongIndex {
+                expected,
+                found,
+                position,
+            }),
+        }
+    }
+
+    /// Same as the [is_complete_message] function, but for both classic and extended frames
+    fn complete_frame<'a>(&self, buffer: &'a [u8]) -> Option<&'a [u8]> {
+        buffer.get(..self.buffered_frame_size(buffer)?)
+    }
+
+    /// Writes the payload `length` into the header of `frame`
+    fn write_length(&self, frame: &mut [u8], length: usize) -> Result<(), UcPackError> {
+        if length > self.max_payload_size() {
+            return Err(UcPackError::TooLong);
+        }
+
+        let header_size = self.header_size();
+        frame[1..header_size].copy_from_slice(&length.to_le_bytes()[..header_size - 1]);
+        Ok(())
+    }
+
+    /// The end index of frames, which depends on the encoding
+    const fn end_index(&self) -> u8 {
+        self.encoding.end_index(self.end_index)
+    }
+
+    fn serializer<B: WriteBuffer>(&self, buffer: B) -> ser::Serializer<B> {
+        ser::Serializer::new(buffer)
+            .with_tagged(self.tagged)
+            .with_wide_variants(self.wide_variants)
+    }
+
+    fn deserializer<'de, B: ReadBuffer<'de>>(&self, buffer: B) -> de::Deserializer<B> {
+        de::Deserializer::new(buffer)
+            .with_tagged(self.tagged)
+            .with_wide_variants(self.wide_variants)
+    }
+
+    /// Deserializes `T` out of the whole payload held by `cursor`
+    fn read_payload<'de, T, B>(&self, mut cursor: B) -> Result<T, UcPackError>
+    where
+        T: Deserialize<'de>,
+        B: ReadBuffer<'de>,
+    {
+        let len = cursor.remaining_bytes();
+        let value = T::deserialize(&mut self.deserializer(&mut cursor))
+            .map_err(|err| err.at(len.wrapping_sub(cursor.remaining_bytes())))?;
+
+        match cursor.remaining_bytes() {
+            // buffers which can't tell how much is left are never rejected
+            remaining if self.deny_trailing_bytes && remaining > 0 && remaining != usize::MAX => {
+                Err(UcPackError::TrailingBytes { remaining })
+            }
+            _ => Ok(value),
+        }
+    }
+
+    /// Verifies the indices (in strict mode) and checksum of a complete packet,
+    /// returning its payload.
+    fn check_packet<'b>(&self, packet: &'b [u8]) -> Result<&'b [u8], UcPackError> {
+        self.split_packet(packet, true)
+    }
+
+    /// Returns the payload of `packet`, verifying its indexes and checksum if `verify` is set.
+    fn split_packet<'b>(&self, packet: &'b [u8], verify: bool) -> Result<&'b [u8], UcPackError> {
+        let overhead = self.overhead();
+        let Some(payload_size) = packet.len().checked_sub(overhead) else {
+            return Err(UcPackError::Eof {
+                needed: overhead - packet.len(),
+            });
+        };
+        let (header, rest) = packet.split_at(self.header_size());
+        let (payload, trailer) = rest.split_at(payload_size);
+        let (end_index, checksum) = (trailer[0], &trailer[1..]);
+
+        if !verify {
+            return Ok(payload);
+        }
+
+        if self.strict {
+            self.check_index(IndexKind::Start, header[0])?;
+            self.check_index(IndexKind::End, end_index)?;
+        }
+
+        let covered = &packet[..self.header_size() + payload_size];
+        let expected = self.checksum(self.checksummed(covered, self.header_size()));
+        if checksum != expected.as_ref() {
+            return Err(UcPackError::WrongCrc {
+                expected: checksum_value(expected.as_ref()),
+                found: checksum_value(checksum),
+            });
+        }
+
+        Ok(payload)
     }
 }
 
+/// The header of a frame, see [UcPack::peek_header].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameHeader {
+    pub start: u8,
+    /// Length of the payload, which never exceeds 255 bytes for classic frames.
+    pub payload_len: u16,
+    /// Size of everything but the payload
+    overhead: usize,
+}
+
+impl FrameHeader {
+    /// Size of the whole frame, header and trailer included.
+    pub fn total_len(&self) -> usize {
+        self.overhead + usize::from(self.payload_len)
+    }
+}
+
+/// Size of the stack buffer COBS framed messages are built and decoded in
+#[cfg(feature = "cobs")]
+const COBS_SCRATCH_SIZE: usize = 512;
+
+/// Size of the chunks [UcPack::serialize_async] pushes payloads in
+#[cfg(feature = "async")]
+pub const ASYNC_CHUNK_SIZE: usize = 256;
+
+/// The checksum `bytes` as a big-endian integer, for error reporting
+fn checksum_value(bytes: &[u8]) -> u32 {
+    bytes
+        .iter()
+        .fold(0, |value, &byte| value << 8 | u32::from(byte))
+}
+
+/// Size of the whole frame beginning `buffer`, as indicated by its length
+/// (a little-endian `u16` for extended frames), or `None` if the length hasn't been received yet.
+fn frame_size(buffer: &[u8], extended_length: bool, checksum_size: usize) -> Option<usize> {
+    let header_size = 2 + usize::from(extended_length);
+    let length = buffer.get(1..header_size)?;
+    let length = length
+        .iter()
+        .rev()
+        .fold(0, |length, &byte| length << 8 | usize::from(byte));
+
+    Some(header_size + length + 1 + checksum_size)
+}
+
 /// Check a buffer for a message. This method is useful during hardware interrupts,
 /// to check whether the received data is a readble message or more data has yet to arrive
 ///
 /// Arguments:
 /// - `buffer`: this argument is NOT for the whole buffer to be passed in but
-/// rather the slice of the buffer containing the currently received information
+///   rather the slice of the buffer containing the currently received information
 ///
 /// Returns:
 /// - `Some`: a slice guaranteed to contain a message
 /// - `None`: a full message hasn't yet been received
+///
+/// The length byte is trusted blindly, so if `buffer` doesn't actually begin with a frame the
+/// returned slice is garbage. Prefer [UcPack::is_complete_message], which checks the indices too.
 pub fn is_complete_message(buffer: &[u8]) -> Option<&[u8]> {
     let length: usize = buffer.get(1).map(|&length| length.into())?;
     buffer.get(..(length + 4))
 }
 
+/// Like [is_complete_message], but the message doesn't have to start at the beginning
+/// of `buffer`: any leading bytes preceding the first `start_byte` are skipped.
+///
+/// Returns:
+/// - `Some((offset, message))`: `offset` is the index of the start byte within `buffer`,
+///   which is also the number of leading bytes the caller can discard
+/// - `None`: no start byte was found or a full message hasn't yet been received
+pub fn find_complete_message(buffer: &[u8], start_byte: u8) -> Option<(usize, &[u8])> {
+    let offset = buffer.iter().position(|&byte| byte == start_byte)?;
+    is_complete_message(&buffer[offset..]).map(|message| (offset, message))
+}
+
+/// Locates the first complete message in `buffer`, skipping any preamble.
+///
+/// Unlike [find_complete_message], which gives up on the first `start_byte` if the
+/// message following it is incomplete, this keeps scanning from the next `start_byte`
+/// onwards. This is what you want when inspecting a snapshot of a DMA buffer, where an
+/// incomplete frame is most likely the leftover of a previous burst.
+///
+/// Returns:
+/// - `Some((offset, message))`: `offset` is the index of the start byte within `buffer`
+/// - `None`: there's no complete message in `buffer`
+pub fn scan_for_packet(buffer: &[u8], start_byte: u8) -> Option<(usize, &[u8])> {
+    let mut offset = 0;
+
+    // offset never exceeds buffer.len(), as found is always a valid index
+    while let Some(position) = buffer[offset..].iter().position(|&byte| byte == start_byte) {
+        let found = offset + position;
+        if let Some(message) = is_complete_message(&buffer[found..]) {
+            return Some((found, message));
+        }
+
+        offset = found + 1;
+    }
+
+    None
+}
+
 /// Helper function to calculate crc8 over byte slices
 #[inline]
 pub fn crc8_slice(input: &[u8]) -> u8 {
-    crc8(input.into_iter().copied())
+    crc8(input.iter().copied())
 }
 
 /// Calculates a CRC8 checksum over any `u8` iterator
 pub fn crc8(input: impl IntoIterator<Item = u8>) -> u8 {
-    let input = input.into_iter();
-
-    input
-        .into_iter()
-        .flat_map(|byte| (0u8..8u8).map(move |j| (byte, j)))
-        .fold(0, |mut crc, (byte, j)| {
-            let sum = (crc ^ (byte >> j)) & 0x01;
-            crc >>= 1;
-            crc ^ (sum != 0).then_some(0x8C).unwrap_or(0) // more explicit than unwrap_or_default
-        })
+    crc8_with(Crc8Config::MAXIM, input)
+}
+
+/// Helper function to calculate a crc8 with custom parameters over byte slices
+#[inline]
+pub fn crc8_slice_with(config: Crc8Config, input: &[u8]) -> u8 {
+    crc8_with(config, input.iter().copied())
+}
+
+/// Calculates a CRC8 checksum with custom parameters over any `u8` iterator,
+/// see [Crc8Config].
+pub fn crc8_with(config: Crc8Config, input: impl IntoIterator<Item = u8>) -> u8 {
+    let mut crc = Crc8::with_config(config);
+    crc.update_iter(input);
+    crc.finalize()
 }
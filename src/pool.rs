@@ -0,0 +1,218 @@
+//! Statically allocated packet buffers, see [PacketPool].
+//!
+//! Buffers are handed out through atomic flags, so this module is only available
+//! on targets with 8 bit atomics (`target_has_atomic = "8"`). Targets lacking them,
+//! such as `thumbv6m` Cortex-M0 cores, don't have it regardless of the enabled features.
+
+use core::{
+    cell::UnsafeCell,
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{buffer::WriteBuffer, UcPackError};
+
+/// A fixed pool of `POOL_SIZE` packet buffers of `PACKET_SIZE` bytes each,
+/// for frames which have to outlive the function serializing them
+/// (e.g. queued for a DMA transfer) without an allocator.
+///
+/// Buffers are acquired through a shared reference, from any context
+/// (interrupt handlers included), so the pool can be a `static`:
+/// ```rust
+/// use ucpack::{pool::PacketPool, UcPack};
+///
+/// static POOL: PacketPool<64, 4> = PacketPool::new();
+///
+/// let ucpack = UcPack::default();
+/// let mut packet = POOL.acquire().unwrap();
+/// packet
+///     .fill_with(|buffer| ucpack.serialize_slice(&(1u8, 2u16), buffer))
+///     .unwrap();
+///
+/// assert_eq!(ucpack.deserialize_slice::<(u8, u16)>(&packet).unwrap(), (1, 2));
+/// assert_eq!(POOL.available(), 3);
+///
+/// drop(packet);
+/// assert_eq!(POOL.available(), 4);
+/// ```
+pub struct PacketPool<const PACKET_SIZE: usize, const POOL_SIZE: usize> {
+    packets: [UnsafeCell<[u8; PACKET_SIZE]>; POOL_SIZE],
+    used: [AtomicBool; POOL_SIZE],
+}
+
+// every packet is only ever accessed by the single guard which acquired it
+unsafe impl<const PACKET_SIZE: usize, const POOL_SIZE: usize> Sync
+    for PacketPool<PACKET_SIZE, POOL_SIZE>
+{
+}
+
+impl<const PACKET_SIZE: usize, const POOL_SIZE: usize> PacketPool<PACKET_SIZE, POOL_SIZE> {
+    pub const fn new() -> Self {
+        Self {
+            packets: [const { UnsafeCell::new([0; PACKET_SIZE]) }; POOL_SIZE],
+            used: [const { AtomicBool::new(false) }; POOL_SIZE],
+        }
+    }
+
+    /// Acquires an empty packet buffer, which is released back to the pool
+    /// once the returned guard is dropped.
+    ///
+    /// Returns `None` if every buffer is in use.
+    pub fn acquire(&self) -> Option<PoolGuard<'_, PACKET_SIZE>> {
+        let index = self
+            .used
+            .iter()
+            .position(|used| !used.swap(true, Ordering::Acquire))?;
+
+        Some(PoolGuard {
+            // SAFETY: the packet has just been marked as used, so this is the only reference to it
+            packet: unsafe { &mut *self.packets[index].get() },
+            len: 0,
+            used: &self.used[index],
+        })
+    }
+
+    /// Number of packet buffers which can currently be acquired.
+    pub fn available(&self) -> usize {
+        self.used
+            .iter()
+            .filter(|used| !used.load(Ordering::Relaxed))
+            .count()
+    }
+}
+
+impl<const PACKET_SIZE: usize, const POOL_SIZE: usize> Default
+    for PacketPool<PACKET_SIZE, POOL_SIZE>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A packet buffer acquired from a [PacketPool], released back to it when dropped.
+///
+/// Dereferences to the bytes written into it so far.
+pub struct PoolGuard<'a, const PACKET_SIZE: usize> {
+    packet: &'a mut [u8; PACKET_SIZE],
+    len: usize,
+    used: &'a AtomicBool,
+}
+
+impl<const PACKET_SIZE: usize> PoolGuard<'_, PACKET_SIZE> {
+    /// Lets `fill` write straight into the unused part of the buffer,
+    /// e.g. through [UcPack::serialize_slice](crate::UcPack::serialize_slice),
+    /// keeping as many bytes as it returns.
+    pub fn fill_with<E>(
+        &mut self,
+        fill: impl FnOnce(&mut [u8]) -> Result<usize, E>,
+    ) -> Result<usize, E> {
+        let spare = &mut self.packet[self.len..];
+        let written = fill(spare)?.min(spare.len());
+
+        self.len += written;
+        Ok(written)
+    }
+
+    /// Discards the bytes written so far.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl<const PACKET_SIZE: usize> WriteBuffer for PoolGuard<'_, PACKET_SIZE> {
+    fn push_slice(&mut self, bf: &[u8]) -> Result<(), UcPackError> {
+        let free = PACKET_SIZE - self.len;
+        if bf.len() > free {
+            return Err(UcPackError::BufferFull {
+                needed: bf.len() - free,
+            });
+        }
+
+        self.packet[self.len..self.len + bf.len()].copy_from_slice(bf);
+        self.len += bf.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn bytes_written(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn capacity(&self) -> Option<usize> {
+        Some(PACKET_SIZE)
+    }
+}
+
+impl<const PACKET_SIZE: usize> Deref for PoolGuard<'_, PACKET_SIZE> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.packet[..self.len]
+    }
+}
+
+impl<const PACKET_SIZE: usize> DerefMut for PoolGuard<'_, PACKET_SIZE> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.packet[..self.len]
+    }
+}
+
+impl<const PACKET_SIZE: usize> Drop for PoolGuard<'_, PACKET_SIZE> {
+    fn drop(&mut self) {
+        self.used.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PacketPool;
+    use crate::{buffer::WriteBuffer, UcPackError};
+
+    #[test]
+    fn acquire_release() {
+        let pool = PacketPool::<4, 3>::new();
+
+        let mut first = pool.acquire().unwrap();
+        let second = pool.acquire().unwrap();
+        let third = pool.acquire().unwrap();
+        assert!(pool.acquire().is_none());
+        assert_eq!(pool.available(), 0);
+
+        first.push_slice(&[1, 2, 3]).unwrap();
+        drop(second);
+        assert_eq!(pool.available(), 1);
+
+        // the released buffer comes back empty, while the others are untouched
+        let fourth = pool.acquire().unwrap();
+        assert!(fourth.is_empty());
+        assert!(pool.acquire().is_none());
+        assert_eq!(*first, [1, 2, 3]);
+
+        drop((first, third, fourth));
+        assert_eq!(pool.available(), 3);
+    }
+
+    #[test]
+    fn write() {
+        let pool = PacketPool::<4, 1>::new();
+        let mut packet = pool.acquire().unwrap();
+
+        packet.push_u8(1).unwrap();
+        assert!(matches!(
+            packet.push_slice(&[2, 3, 4, 5]),
+            Err(UcPackError::BufferFull { needed: 1 })
+        ));
+        assert_eq!(packet.remaining_capacity(), 3);
+
+        let written = packet.fill_with(|spare| {
+            spare[..2].copy_from_slice(&[2, 3]);
+            Ok::<_, UcPackError>(2)
+        });
+        assert_eq!(written.unwrap(), 2);
+        assert_eq!(*packet, [1, 2, 3]);
+
+        packet.clear();
+        assert!(packet.is_empty());
+    }
+}
@@ -3,23 +3,80 @@ use core::fmt::Display;
 use serde::ser;
 use serde::ser::Impossible;
 
-use crate::{buffer::WriteBuffer, macros::unimpl, UcPackError};
+use crate::{buffer::WriteBuffer, macros::unimpl, tag, UcPackError};
 
 pub struct Serializer<B: WriteBuffer> {
     buffer: B,
+    tagged: bool,
+    wide_variants: bool,
 }
 
 impl<B: WriteBuffer> Serializer<B> {
     pub fn new(buffer: B) -> Serializer<B> {
-        Self { buffer }
+        Self {
+            buffer,
+            tagged: false,
+            wide_variants: false,
+        }
+    }
+
+    /// Enables or disables the tagged flavor of the format, where every value
+    /// is preceded by its [type tag](crate::tag).
+    pub fn with_tagged(self, tagged: bool) -> Self {
+        Self { tagged, ..self }
+    }
+
+    /// Encodes enum variant indices as little-endian `u16`s instead of a single byte.
+    pub fn with_wide_variants(self, wide_variants: bool) -> Self {
+        Self {
+            wide_variants,
+            ..self
+        }
+    }
+
+    fn push_tag(&mut self, tag: u8) -> Result<(), UcPackError> {
+        match self.tagged {
+            true => self.buffer.push_u8(tag),
+            false => Ok(()),
+        }
+    }
+
+    /// Sequences are only prefixed by their length in the tagged format
+    fn push_seq(&mut self, len: usize) -> Result<(), UcPackError> {
+        if !self.tagged {
+            return Ok(());
+        }
+
+        let len = u8::try_from(len).map_err(|_| UcPackError::TooLong)?;
+        self.buffer.push_slice(&[tag::SEQ, len])
+    }
+
+    fn push_bytes(&mut self, bytes: &[u8]) -> Result<(), UcPackError> {
+        let len = u8::try_from(bytes.len()).map_err(|_| UcPackError::TooLong)?;
+        self.buffer.push_u8(len)?;
+        self.buffer.push_slice(bytes)
+    }
+
+    fn push_variant(&mut self, idx: u32) -> Result<(), UcPackError> {
+        self.push_tag(tag::VARIANT)?;
+        match self.wide_variants {
+            true => {
+                let idx = u16::try_from(idx).map_err(|_| UcPackError::BadVariant)?;
+                self.buffer.push_slice(&idx.to_le_bytes())
+            }
+            false => {
+                let idx = u8::try_from(idx).map_err(|_| UcPackError::BadVariant)?;
+                self.buffer.push_u8(idx)
+            }
+        }
     }
 }
 
-impl<'a, B: WriteBuffer> ser::Serializer for &'a mut Serializer<B> {
+impl<B: WriteBuffer> ser::Serializer for &mut Serializer<B> {
     type Ok = ();
     type Error = UcPackError;
 
-    type SerializeSeq = Impossible<(), UcPackError>;
+    type SerializeSeq = Self;
     type SerializeTuple = Self;
     type SerializeTupleStruct = Self;
     type SerializeTupleVariant = Self;
@@ -28,29 +85,33 @@ impl<'a, B: WriteBuffer> ser::Serializer for &'a mut Serializer<B> {
     type SerializeStructVariant = Self;
 
     fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
-        self.serialize_u8(v as u8)
+        self.push_tag(tag::BOOL)?;
+        self.buffer.push_u8(v as u8)
     }
 
     fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        self.push_tag(tag::U8)?;
         self.buffer.push_u8(v)
     }
 
     fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
-        self.serialize_u8(v as u8)
+        self.push_tag(tag::I8)?;
+        self.buffer.push_u8(v as u8)
     }
 
     fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
-        let bytes = v.to_le_bytes();
-        self.buffer.push_slice(&bytes)
+        self.push_tag(tag::U16)?;
+        self.buffer.push_slice(&v.to_le_bytes())
     }
 
     fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
-        self.serialize_u16(v as u16)
+        self.push_tag(tag::I16)?;
+        self.buffer.push_slice(&v.to_le_bytes())
     }
 
     fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
-        let bytes = v.to_le_bytes();
-        self.buffer.push_slice(&bytes)
+        self.push_tag(tag::F32)?;
+        self.buffer.push_slice(&v.to_le_bytes())
     }
 
     unimpl!(serialize_u32, u32);
@@ -61,12 +122,35 @@ impl<'a, B: WriteBuffer> ser::Serializer for &'a mut Serializer<B> {
     unimpl!(serialize_i128, i128);
     unimpl!(serialize_f64, f64);
     unimpl!(serialize_char, char);
-    unimpl!(serialize_str, &str);
-    unimpl!(serialize_bytes, &[u8]);
-    unimpl!(serialize_none);
-    unimpl!(serialize_unit);
-    unimpl!(serialize_unit_struct, &'static str);
-    // unimpl!(serialize_seq, Option<usize>);
+
+    /// Strings are encoded as their UTF-8 bytes, prefixed by their length as a `u8`.
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        self.push_tag(tag::STR)?;
+        self.push_bytes(v.as_bytes())
+    }
+
+    /// Byte arrays are prefixed by their length as a `u8`.
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        self.push_tag(tag::BYTES)?;
+        self.push_bytes(v)
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        if !self.tagged {
+            unimpl!(name = "None")
+        }
+
+        self.buffer.push_u8(tag::NONE)
+    }
+
+    /// Unit doesn't hold any data, so it takes no space at all in the untagged format.
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        self.push_tag(tag::UNIT)
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.serialize_unit()
+    }
 
     fn collect_str<T>(self, _: &T) -> Result<Self::Ok, Self::Error>
     where
@@ -75,23 +159,30 @@ impl<'a, B: WriteBuffer> ser::Serializer for &'a mut Serializer<B> {
         unimpl!(name = "string")
     }
 
-    fn serialize_some<T>(self, _: &T) -> Result<Self::Ok, Self::Error>
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
     where
         T: ?Sized + ser::Serialize,
     {
-        unimpl!(name = "Some")
+        if !self.tagged {
+            unimpl!(name = "Some")
+        }
+
+        self.buffer.push_u8(tag::SOME)?;
+        value.serialize(self)
     }
 
     fn serialize_unit_variant(
         self,
-        name: &'static str,
-        _: u32,
+        _: &'static str,
+        idx: u32,
         _: &'static str,
     ) -> Result<Self::Ok, Self::Error> {
-        // no clear way of doing it so up to implementor's
-        // ability to use serialize_with attributes
-
-        unimpl!(name = name)
+        // just the index, mirroring how the deserializer reads unit variants
+        self.push_variant(idx)?;
+        match self.tagged {
+            true => self.serialize_unit(),
+            false => Ok(()),
+        }
     }
 
     fn serialize_newtype_struct<T>(
@@ -115,16 +206,24 @@ impl<'a, B: WriteBuffer> ser::Serializer for &'a mut Serializer<B> {
     where
         T: ?Sized + ser::Serialize,
     {
-        let idx = u8::try_from(idx).map_err(|_| UcPackError::BadVariant)?;
-        self.serialize_u8(idx)?;
+        self.push_variant(idx)?;
         obj.serialize(self)
     }
 
-    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
-        unimpl!(name = "sequence")
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        // sequences of unknown length can't be supported as the
+        // length has to be known upfront in the tagged format
+        match (self.tagged, len) {
+            (true, Some(len)) => {
+                self.push_seq(len)?;
+                Ok(self)
+            }
+            _ => unimpl!(name = "sequence"),
+        }
     }
 
-    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.push_seq(len)?;
         Ok(self)
     }
 
@@ -141,11 +240,10 @@ impl<'a, B: WriteBuffer> ser::Serializer for &'a mut Serializer<B> {
         _: &'static str,
         idx: u32,
         _: &'static str,
-        _: usize,
+        len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
-        let idx = u8::try_from(idx).map_err(|_| UcPackError::BadVariant)?;
-        self.serialize_u8(idx)?;
-        Ok(self)
+        self.push_variant(idx)?;
+        self.serialize_tuple(len)
     }
 
     fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
@@ -155,9 +253,9 @@ impl<'a, B: WriteBuffer> ser::Serializer for &'a mut Serializer<B> {
     fn serialize_struct(
         self,
         _: &'static str,
-        _: usize,
+        len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
-        Ok(self)
+        self.serialize_tuple(len)
     }
 
     fn serialize_struct_variant(
@@ -171,7 +269,23 @@ impl<'a, B: WriteBuffer> ser::Serializer for &'a mut Serializer<B> {
     }
 }
 
-impl<'a, B: WriteBuffer> ser::SerializeTuple for &'a mut Serializer<B> {
+impl<B: WriteBuffer> ser::SerializeSeq for &mut Serializer<B> {
+    type Ok = ();
+    type Error = UcPackError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
+
+impl<B: WriteBuffer> ser::SerializeTuple for &mut Serializer<B> {
     type Ok = ();
     type Error = UcPackError;
 
@@ -187,7 +301,7 @@ impl<'a, B: WriteBuffer> ser::SerializeTuple for &'a mut Serializer<B> {
     }
 }
 
-impl<'a, B: WriteBuffer> ser::SerializeStruct for &'a mut Serializer<B> {
+impl<B: WriteBuffer> ser::SerializeStruct for &mut Serializer<B> {
     type Ok = ();
     type Error = UcPackError;
 
@@ -203,7 +317,7 @@ impl<'a, B: WriteBuffer> ser::SerializeStruct for &'a mut Serializer<B> {
     }
 }
 
-impl<'a, B: WriteBuffer> ser::SerializeTupleStruct for &'a mut Serializer<B> {
+impl<B: WriteBuffer> ser::SerializeTupleStruct for &mut Serializer<B> {
     type Ok = ();
     type Error = UcPackError;
 
@@ -219,7 +333,7 @@ impl<'a, B: WriteBuffer> ser::SerializeTupleStruct for &'a mut Serializer<B> {
     }
 }
 
-impl<'a, B: WriteBuffer> ser::SerializeTupleVariant for &'a mut Serializer<B> {
+impl<B: WriteBuffer> ser::SerializeTupleVariant for &mut Serializer<B> {
     type Ok = ();
     type Error = UcPackError;
 
@@ -235,7 +349,7 @@ impl<'a, B: WriteBuffer> ser::SerializeTupleVariant for &'a mut Serializer<B> {
     }
 }
 
-impl<'a, B: WriteBuffer> ser::SerializeStructVariant for &'a mut Serializer<B> {
+impl<B: WriteBuffer> ser::SerializeStructVariant for &mut Serializer<B> {
     type Ok = ();
 
     type Error = UcPackError;
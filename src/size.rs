@@ -0,0 +1,129 @@
+use crate::{checksum::Checksum, UcPack};
+
+/// Compile time upper bound on the size a type serializes into.
+///
+/// Only implemented for types which have a fixed maximum size in the ucpack format,
+/// so variable-length types (`Vec`, `str`, ...) purposefully opt out.
+///
+/// Sizes are the ones of the untagged format, the tagged one prefixes values with
+/// their [type tag](crate::tag). `Option`, which can only be serialized by the latter,
+/// accounts for its own tag alone.
+///
+/// Structs (and tuple structs) can implement it by summing their fields:
+/// ```rust
+/// use ucpack::size::MaxUcPackSize;
+///
+/// struct Motors {
+///     left: i16,
+///     right: i16,
+///     enabled: bool,
+/// }
+///
+/// impl MaxUcPackSize for Motors {
+///     const MAX_PAYLOAD_SIZE: usize =
+///         i16::MAX_PAYLOAD_SIZE + i16::MAX_PAYLOAD_SIZE + bool::MAX_PAYLOAD_SIZE;
+/// }
+///
+/// let buffer = [0u8; Motors::MAX_SIZE];
+/// ```
+pub trait MaxUcPackSize {
+    /// Maximum number of payload bytes a value of this type can serialize into.
+    const MAX_PAYLOAD_SIZE: usize;
+
+    /// Maximum size of a whole frame carrying a value of this type,
+    /// that is the payload plus start index, length, end index and crc.
+    ///
+    /// Assumes the default configuration (one byte length, [Crc8](crate::checksum::Crc8),
+    /// raw encoding), see [packet_size_for] for other ones.
+    const MAX_SIZE: usize = Self::MAX_PAYLOAD_SIZE + UcPack::FRAME_OVERHEAD;
+}
+
+macro_rules! impl_max_size {
+    ($($type:ty => $size:expr),* $(,)?) => {
+        $(
+            impl MaxUcPackSize for $type {
+                const MAX_PAYLOAD_SIZE: usize = $size;
+            }
+        )*
+    };
+}
+
+impl_max_size! {
+    bool => 1,
+    u8 => 1,
+    i8 => 1,
+    u16 => 2,
+    i16 => 2,
+    f32 => 4,
+    () => 0,
+}
+
+impl<T: MaxUcPackSize> MaxUcPackSize for Option<T> {
+    const MAX_PAYLOAD_SIZE: usize = 1 + T::MAX_PAYLOAD_SIZE;
+}
+
+impl<T: MaxUcPackSize, const N: usize> MaxUcPackSize for [T; N] {
+    const MAX_PAYLOAD_SIZE: usize = T::MAX_PAYLOAD_SIZE * N;
+}
+
+macro_rules! impl_max_size_tuple {
+    ($($name:ident)+) => {
+        impl<$($name: MaxUcPackSize),+> MaxUcPackSize for ($($name,)+) {
+            const MAX_PAYLOAD_SIZE: usize = 0 $(+ $name::MAX_PAYLOAD_SIZE)+;
+        }
+    };
+}
+
+impl_max_size_tuple!(A);
+impl_max_size_tuple!(A B);
+impl_max_size_tuple!(A B C);
+impl_max_size_tuple!(A B C D);
+impl_max_size_tuple!(A B C D E);
+impl_max_size_tuple!(A B C D E F);
+impl_max_size_tuple!(A B C D E F G);
+impl_max_size_tuple!(A B C D E F G H);
+
+/// [MaxUcPackSize::MAX_PAYLOAD_SIZE] of `T`, usable in const contexts.
+pub const fn payload_size<T: MaxUcPackSize>() -> usize {
+    T::MAX_PAYLOAD_SIZE
+}
+
+/// [MaxUcPackSize::MAX_SIZE] of `T`, usable in const contexts.
+pub const fn packet_size<T: MaxUcPackSize>() -> usize {
+    T::MAX_SIZE
+}
+
+/// Maximum size of a whole frame carrying a value of `T` with the given configuration,
+/// as long as its payload is raw encoded.
+/// ```rust
+/// use ucpack::{checksum::Crc16, size::packet_size_for, UcPack};
+///
+/// const UCPACK: UcPack<Crc16> = UcPack::new(b'A', b'#')
+///     .with_checksum(Crc16::new())
+///     .with_extended_length(true);
+///
+/// let mut buffer = [0u8; packet_size_for::<(u8, i16), _>(&UCPACK)];
+/// let n = UCPACK.serialize_slice(&(1u8, -2i16), &mut buffer).unwrap();
+/// assert_eq!(n, buffer.len());
+/// ```
+pub const fn packet_size_for<T: MaxUcPackSize, C: Checksum>(ucpack: &UcPack<C>) -> usize {
+    ucpack.frame_size(T::MAX_PAYLOAD_SIZE)
+}
+
+/// Maximum size of a whole frame carrying a value of the given type,
+/// with the default configuration. See [MaxUcPackSize].
+/// ```rust
+/// use ucpack::{ucpack_packet_size, UcPack};
+///
+/// let mut buffer = [0u8; ucpack_packet_size!((u8, i16, f32))];
+/// let n = UcPack::default()
+///     .serialize_slice(&(1u8, -2i16, 0.5f32), &mut buffer)
+///     .unwrap();
+/// assert_eq!(n, buffer.len());
+/// ```
+#[macro_export]
+macro_rules! ucpack_packet_size {
+    ($type:ty) => {
+        $crate::size::packet_size::<$type>()
+    };
+}
@@ -0,0 +1,38 @@
+//! Type tags used by the tagged (self-describing) flavor of the format.
+//!
+//! When tagging is enabled every value is preceded by one of these bytes,
+//! which allows deserializing data without knowing its type beforehand
+//! (see [serde::Deserializer::deserialize_any]).
+//!
+//! Values are otherwise encoded just like in the untagged format, except for:
+//! - sequences (tuples, structs, ...), where the tag is followed by the number of elements as a `u8`
+//!   (the elements themselves are tagged values)
+//! - enum variants, where the tag is followed by the variant index and a single tagged value
+//!   holding the variant's content ([UNIT] for unit variants, a [SEQ] for tuple and struct variants)
+
+/// `()`, unit structs and the content of unit variants. Not followed by any data.
+pub const UNIT: u8 = 0x00;
+/// A `bool`.
+pub const BOOL: u8 = 0x01;
+/// A `u8`.
+pub const U8: u8 = 0x02;
+/// An `i8`.
+pub const I8: u8 = 0x03;
+/// A `u16`.
+pub const U16: u8 = 0x04;
+/// An `i16`.
+pub const I16: u8 = 0x05;
+/// An `f32`.
+pub const F32: u8 = 0x06;
+/// A sequence of tagged values, such as a tuple or a struct.
+pub const SEQ: u8 = 0x07;
+/// An enum variant.
+pub const VARIANT: u8 = 0x08;
+/// `None`. Not followed by any data.
+pub const NONE: u8 = 0x09;
+/// `Some`, followed by the tagged value it holds.
+pub const SOME: u8 = 0x0A;
+/// A byte array, followed by its length as a `u8` and the bytes themselves.
+pub const BYTES: u8 = 0x0B;
+/// A string, encoded just like [BYTES].
+pub const STR: u8 = 0x0C;
@@ -0,0 +1,147 @@
+//! [proptest] strategies generating payloads made of every type the format supports,
+//! along with a roundtrip property to check them against.
+//!
+//! ```rust
+//! use proptest::prelude::*;
+//! use ucpack::testing::{payload, roundtrip, ucpack};
+//!
+//! proptest!(|(ucpack in ucpack(), payload in payload())| {
+//!     roundtrip(&ucpack, &payload)?;
+//! });
+//! ```
+
+use alloc::{format, string::String};
+use core::fmt::Debug;
+
+use proptest::{prelude::*, test_runner::TestCaseError};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{encoding::Encoding, UcPack};
+
+/// A struct holding every type supported by both the untagged and the tagged format.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Payload {
+    pub flag: bool,
+    pub byte: u8,
+    pub signed_byte: i8,
+    pub word: u16,
+    pub signed_word: i16,
+    pub float: f32,
+    pub name: String,
+    pub unit: (),
+    pub marker: Marker,
+    pub wrapper: Wrapper,
+    pub pair: (u8, i16),
+    pub array: [u16; 4],
+    #[serde(with = "crate::byte_array")]
+    pub bytes: [u8; 8],
+    pub command: Command,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Marker;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Wrapper(pub u16);
+
+/// An enum with every kind of variant.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Command {
+    Stop,
+    Speed(i16),
+    Move(i16, i16),
+    Turn { angle: i16, speed: u8 },
+}
+
+/// Any `f32` but NaN, which isn't equal to itself.
+pub fn float() -> impl Strategy<Value = f32> {
+    use proptest::num::f32;
+
+    f32::NORMAL | f32::SUBNORMAL | f32::ZERO | f32::INFINITE
+}
+
+pub fn command() -> impl Strategy<Value = Command> {
+    prop_oneof![
+        Just(Command::Stop),
+        any::<i16>().prop_map(Command::Speed),
+        any::<(i16, i16)>().prop_map(|(left, right)| Command::Move(left, right)),
+        any::<(i16, u8)>().prop_map(|(angle, speed)| Command::Turn { angle, speed }),
+    ]
+}
+
+pub fn payload() -> impl Strategy<Value = Payload> {
+    let primitives = (
+        any::<bool>(),
+        any::<u8>(),
+        any::<i8>(),
+        any::<u16>(),
+        any::<i16>(),
+        float(),
+        "\\PC{0,16}",
+    );
+    let compounds = (
+        any::<u16>(),
+        any::<(u8, i16)>(),
+        any::<[u16; 4]>(),
+        any::<[u8; 8]>(),
+        command(),
+    );
+
+    (primitives, compounds).prop_map(
+        |(
+            (flag, byte, signed_byte, word, signed_word, float, name),
+            (wrapper, pair, array, bytes, command),
+        )| Payload {
+            flag,
+            byte,
+            signed_byte,
+            word,
+            signed_word,
+            float,
+            name,
+            unit: (),
+            marker: Marker,
+            wrapper: Wrapper(wrapper),
+            pair,
+            array,
+            bytes,
+            command,
+        },
+    )
+}
+
+/// Every combination of the [UcPack] settings affecting how payloads are encoded.
+pub fn ucpack() -> impl Strategy<Value = UcPack> {
+    let encoding = prop_oneof![
+        Just(Encoding::Raw),
+        Just(Encoding::Cobs),
+        Just(Encoding::Escaped { escape: 0x7D }),
+    ];
+
+    (any::<[bool; 4]>(), encoding).prop_map(
+        |([tagged, wide_variants, extended_length, crc_covers_header], encoding)| {
+            UcPack::default()
+                .with_tagged(tagged)
+                .with_wide_variants(wide_variants)
+                .with_extended_length(extended_length)
+                .with_crc_covers_header(crc_covers_header)
+                .with_encoding(encoding)
+        },
+    )
+}
+
+/// Checks `value` deserializes back into itself once serialized with `ucpack`.
+pub fn roundtrip<T>(ucpack: &UcPack, value: &T) -> Result<(), TestCaseError>
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let frame = ucpack
+        .serialize_vec(value)
+        .map_err(|err| TestCaseError::fail(format!("serialization failed: {err}")))?;
+    let deserialized: T = ucpack
+        .deserialize_slice(&frame)
+        .map_err(|err| TestCaseError::fail(format!("deserialization failed: {err}")))?;
+
+    prop_assert_eq!(&deserialized, value);
+    Ok(())
+}
@@ -0,0 +1,48 @@
+//! Compact encodings for `core::time::Duration`.
+//!
+//! serde serializes a `Duration` as a `{ secs: u64, nanos: u32 }` struct, which
+//! the ucpack format can't represent. The modules here are meant to be used
+//! through `#[serde(with = "...")]` instead.
+
+/// Serializes a `Duration` as a `u32` number of milliseconds, covering up to ~49 days.
+///
+/// The format has no `u32`, so it's written as its two little-endian `u16` halves,
+/// the same 4 bytes a `u32` would take in the untagged format.
+/// Durations which don't fit are rejected, and sub-millisecond precision is lost.
+/// ```rust
+/// use core::time::Duration;
+/// use ucpack::UcPack;
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Config {
+///     #[serde(with = "ucpack::time::millis")]
+///     timeout: Duration,
+/// }
+///
+/// let ucpack = UcPack::default();
+/// let mut buffer = [0u8; 8];
+/// let config = Config { timeout: Duration::from_millis(1500) };
+/// ucpack.serialize_slice(&config, &mut buffer).unwrap();
+///
+/// let config: Config = ucpack.deserialize_slice(&buffer).unwrap();
+/// assert_eq!(config.timeout, Duration::from_millis(1500));
+/// ```
+pub mod millis {
+    use core::time::Duration;
+
+    use serde::{ser::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        let millis = u32::try_from(duration.as_millis())
+            .map_err(|_| S::Error::custom("duration doesn't fit in u32 milliseconds"))?;
+
+        (millis as u16, (millis >> 16) as u16).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let (low, high) = <(u16, u16)>::deserialize(deserializer)?;
+        let millis = u32::from(high) << 16 | u32::from(low);
+
+        Ok(Duration::from_millis(millis.into()))
+    }
+}
@@ -20,6 +20,14 @@ fn test_continuity() {
     let vec_serialized = ucpack.serialize_vec(&PAYLOAD).unwrap();
 
     assert_eq!(vec_serialized, slice_serialized);
+
+    // the crc, computed while serializing, covers the payload
+    let payload = [1, 0, 2, 0, 0, 0x80, 0x3F];
+    let crc = ucpack::crc8_slice(&payload);
+    assert_eq!(
+        vec_serialized,
+        [&[b'A', 7][..], &payload, &[b'#', crc]].concat()
+    );
 }
 
 #[test]
@@ -51,3 +59,2853 @@ fn test_serialize_deserialize() {
 
     assert_eq!(PAYLOAD, deserialized);
 }
+
+#[test]
+fn test_serialize_size() {
+    #[derive(Serialize)]
+    enum TestEnum {
+        Tag1(u8),
+        Tag2(u16, f32),
+    }
+
+    #[derive(Serialize)]
+    struct TestPayload {
+        a: u16,
+        b: TestEnum,
+    }
+
+    let ucpack = UcPack::default();
+
+    fn check(ucpack: &UcPack, payload: &impl Serialize) {
+        let size = ucpack.serialize_size(payload).unwrap();
+        assert_eq!(size, ucpack.serialize_vec(payload).unwrap().len());
+    }
+
+    check(&ucpack, &1u8);
+    check(&ucpack, &(1u16, -1i8, 2.5f32));
+    check(&ucpack, &TestEnum::Tag1(3));
+    check(
+        &ucpack,
+        &TestPayload {
+            a: 1,
+            b: TestEnum::Tag2(2, 3.0),
+        },
+    );
+
+    let too_long = [[0u16; 32]; 4];
+    assert!(matches!(
+        ucpack.serialize_size(&too_long),
+        Err(ucpack::UcPackError::TooLong)
+    ));
+}
+
+#[test]
+fn test_find_complete_message() {
+    let ucpack = UcPack::default();
+    let frame = ucpack.serialize_vec(&(1u8, 2u16)).unwrap();
+
+    let mut buffer = vec![0xFF, 0x00, 0x13];
+    buffer.extend_from_slice(&frame);
+    buffer.extend_from_slice(&[b'A', 10]); // beginning of another frame
+
+    let (offset, message) = ucpack::find_complete_message(&buffer, b'A').unwrap();
+    assert_eq!(offset, 3);
+    assert_eq!(message, frame);
+
+    assert!(ucpack::find_complete_message(&buffer[..5], b'A').is_none());
+    assert!(ucpack::find_complete_message(&[0, 1, 2, 3, 4, 5], b'A').is_none());
+}
+
+#[test]
+fn test_max_size() {
+    use ucpack::size::MaxUcPackSize;
+
+    #[derive(Serialize)]
+    struct TestPayload {
+        a: u16,
+        b: (u8, i8),
+        c: [f32; 3],
+    }
+
+    impl MaxUcPackSize for TestPayload {
+        const MAX_PAYLOAD_SIZE: usize =
+            u16::MAX_PAYLOAD_SIZE + <(u8, i8)>::MAX_PAYLOAD_SIZE + <[f32; 3]>::MAX_PAYLOAD_SIZE;
+    }
+
+    let payload = TestPayload {
+        a: 1,
+        b: (2, 3),
+        c: [1.0, 2.0, 3.0],
+    };
+
+    let ucpack = UcPack::default();
+    let mut buffer = [0u8; TestPayload::MAX_SIZE];
+
+    let n = ucpack.serialize_slice(&payload, &mut buffer).unwrap();
+    assert!(TestPayload::MAX_SIZE >= ucpack.serialize_size(&payload).unwrap());
+    assert_eq!(n, TestPayload::MAX_SIZE);
+
+    const PAYLOAD_SIZE: usize = ucpack::size::payload_size::<TestPayload>();
+    assert_eq!(PAYLOAD_SIZE, 16);
+    assert_eq!(ucpack::ucpack_packet_size!(TestPayload), 20);
+
+    // unit takes no space, options (tagged format only) a byte for their tag
+    assert_eq!(
+        ucpack.serialize_size(&(1u8, ())).unwrap(),
+        <(u8, ())>::MAX_SIZE
+    );
+    assert_eq!(Option::<u16>::MAX_PAYLOAD_SIZE, 3);
+
+    let ucpack = ucpack.with_checksum(ucpack::checksum::Crc16::new());
+    let size = ucpack::size::packet_size_for::<TestPayload, _>(&ucpack);
+    assert_eq!(
+        ucpack.serialize_slice(&payload, &mut [0u8; 32]).unwrap(),
+        size
+    );
+}
+
+#[test]
+fn test_ignored_any() {
+    use serde::de::IgnoredAny;
+
+    #[derive(Serialize)]
+    enum FullEnum {
+        Tag1(u8),
+        Tag2(u16, f32),
+    }
+
+    #[derive(Serialize)]
+    struct FullPayload {
+        a: u16,
+        b: FullEnum,
+    }
+
+    // older firmware revision, which doesn't care about Tag2's content
+    #[derive(Deserialize, Debug, PartialEq)]
+    enum ReducedEnum {
+        Tag1(u8),
+        Tag2(IgnoredAny),
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct ReducedPayload {
+        a: u16,
+        b: ReducedEnum,
+    }
+
+    let ucpack = UcPack::default();
+
+    let serialized = ucpack
+        .serialize_vec(&FullPayload {
+            a: 10,
+            b: FullEnum::Tag2(1, 2.0),
+        })
+        .unwrap();
+    let deserialized: ReducedPayload = ucpack.deserialize_slice(&serialized).unwrap();
+    assert_eq!(deserialized.a, 10);
+    assert!(matches!(deserialized.b, ReducedEnum::Tag2(_)));
+
+    let serialized = ucpack
+        .serialize_vec(&FullPayload {
+            a: 10,
+            b: FullEnum::Tag1(5),
+        })
+        .unwrap();
+    let deserialized: ReducedPayload = ucpack.deserialize_slice(&serialized).unwrap();
+    assert_eq!(deserialized.b, ReducedEnum::Tag1(5));
+}
+
+#[test]
+fn test_scan_for_packet() {
+    let ucpack = UcPack::default();
+    let frame = ucpack.serialize_vec(&(1u8, 2u16)).unwrap();
+
+    // preamble, then a frame from a previous burst whose tail got lost
+    let mut buffer = vec![0x00, 0x13, b'A', 200, 0x01];
+    buffer.extend_from_slice(&frame);
+
+    let (offset, message) = ucpack::scan_for_packet(&buffer, b'A').unwrap();
+    assert_eq!(offset, 5);
+    assert_eq!(message, frame);
+
+    assert!(ucpack::scan_for_packet(&buffer[..8], b'A').is_none());
+    assert!(ucpack::scan_for_packet(&[], b'A').is_none());
+    assert!(ucpack::scan_for_packet(b"A", b'A').is_none());
+}
+
+#[test]
+fn test_buffer_full() {
+    let ucpack = UcPack::default();
+    let payload = (1u16, 2u16, 3.0f32);
+
+    let mut buffer = [0u8; 6];
+    let err = ucpack.serialize_slice(&payload, &mut buffer).unwrap_err();
+
+    let needed = match err {
+        ucpack::UcPackError::BufferFull { needed } => needed,
+        err => panic!("unexpected error {err}"),
+    };
+    assert_eq!(
+        buffer.len() + needed,
+        ucpack.serialize_size(&payload).unwrap()
+    );
+
+    let mut buffer = vec![0u8; buffer.len() + needed];
+    ucpack.serialize_slice(&payload, &mut buffer).unwrap();
+}
+
+#[test]
+fn test_variant_identifier() {
+    use serde::de::{self, EnumAccess, VariantAccess};
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(variant_identifier)]
+    enum Kind {
+        Ping,
+        Move,
+    }
+
+    #[derive(Serialize, Debug, PartialEq)]
+    enum Command {
+        Ping,
+        Move(u16),
+    }
+
+    impl<'de> Deserialize<'de> for Command {
+        fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct CommandVisitor;
+
+            impl<'de> de::Visitor<'de> for CommandVisitor {
+                type Value = Command;
+
+                fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    f.write_str("a command")
+                }
+
+                fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Command, A::Error> {
+                    match data.variant()? {
+                        (Kind::Ping, variant) => variant.unit_variant().map(|_| Command::Ping),
+                        (Kind::Move, variant) => variant.newtype_variant().map(Command::Move),
+                    }
+                }
+            }
+
+            deserializer.deserialize_enum("Command", &["Ping", "Move"], CommandVisitor)
+        }
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Tagged {
+        kind: Kind,
+        value: u16,
+    }
+
+    let ucpack = UcPack::default();
+
+    let serialized = ucpack.serialize_vec(&Command::Move(300)).unwrap();
+    let deserialized: Command = ucpack.deserialize_slice(&serialized).unwrap();
+    assert_eq!(deserialized, Command::Move(300));
+
+    // identifiers outside of an enum go through deserialize_identifier
+    let serialized = ucpack.serialize_vec(&(1u8, 10u16)).unwrap();
+    let deserialized: Tagged = ucpack.deserialize_slice(&serialized).unwrap();
+    assert_eq!(
+        deserialized,
+        Tagged {
+            kind: Kind::Move,
+            value: 10
+        }
+    );
+
+    let serialized = ucpack.serialize_vec(&(2u8, 10u16)).unwrap();
+    assert!(ucpack.deserialize_slice::<Tagged>(&serialized).is_err());
+}
+
+#[test]
+fn test_packet_iter() {
+    let ucpack = UcPack::default();
+
+    let mut buffer = Vec::new();
+    buffer.extend(ucpack.serialize_vec(&(1u8, 2u16)).unwrap());
+    buffer.extend(ucpack.serialize_vec(&3.0f32).unwrap());
+
+    let mut corrupted = ucpack.serialize_vec(&4u8).unwrap();
+    *corrupted.last_mut().unwrap() ^= 0xFF;
+    buffer.extend(corrupted);
+
+    buffer.extend(ucpack.serialize_vec(&5u16).unwrap());
+    buffer.extend(&[b'A', 10, 0]); // incomplete trailing packet
+
+    let mut iter = ucpack.packet_iter(&buffer);
+
+    let packet = iter.next().unwrap().unwrap();
+    assert_eq!(
+        ucpack.deserialize_slice::<(u8, u16)>(packet).unwrap(),
+        (1, 2)
+    );
+    let packet = iter.next().unwrap().unwrap();
+    assert_eq!(ucpack.deserialize_slice::<f32>(packet).unwrap(), 3.0);
+    assert!(matches!(
+        iter.next(),
+        Some(Err(ucpack::UcPackError::WrongCrc { .. }))
+    ));
+    let packet = iter.next().unwrap().unwrap();
+    assert_eq!(ucpack.deserialize_slice::<u16>(packet).unwrap(), 5);
+
+    assert!(iter.next().is_none());
+    assert_eq!(iter.remaining(), &[b'A', 10, 0]);
+}
+
+#[test]
+fn test_iter_messages() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct TestPayload {
+        a: u16,
+        b: i8,
+    }
+
+    let ucpack = UcPack::default();
+    let payloads = [
+        TestPayload { a: 1, b: -1 },
+        TestPayload { a: 2, b: -2 },
+        TestPayload { a: 3, b: -3 },
+    ];
+
+    let mut buffer = Vec::new();
+    for payload in &payloads {
+        buffer.extend(ucpack.serialize_vec(payload).unwrap());
+    }
+
+    let partial = ucpack.serialize_vec(&TestPayload { a: 4, b: -4 }).unwrap();
+    buffer.extend(&partial[..3]);
+
+    let mut iter = ucpack.iter_messages::<TestPayload>(&buffer);
+    let deserialized: Vec<_> = iter.by_ref().map(Result::unwrap).collect();
+
+    assert_eq!(deserialized, payloads);
+    assert_eq!(iter.remaining(), &partial[..3]);
+}
+
+#[test]
+fn test_tagged_untagged_enum() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(untagged)]
+    enum Reading {
+        Distance(u16),
+        Temperature(f32),
+        Flags(bool, bool),
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct TestPayload {
+        a: Reading,
+        b: Option<Reading>,
+        c: Option<u8>,
+    }
+
+    let ucpack = UcPack::default().with_tagged(true);
+
+    let payloads = [
+        TestPayload {
+            a: Reading::Distance(100),
+            b: Some(Reading::Flags(true, false)),
+            c: None,
+        },
+        TestPayload {
+            a: Reading::Temperature(21.5),
+            b: None,
+            c: Some(3),
+        },
+    ];
+
+    for payload in payloads {
+        let serialized = ucpack.serialize_vec(&payload).unwrap();
+        let deserialized: TestPayload = ucpack.deserialize_slice(&serialized).unwrap();
+        assert_eq!(payload, deserialized);
+    }
+
+    // untagged payloads are unaffected
+    let serialized = UcPack::default().serialize_vec(&(1u8, 2u16)).unwrap();
+    assert_eq!(
+        serialized,
+        [b'A', 3, 1, 2, 0, b'#', ucpack::crc8_slice(&[1, 2, 0])]
+    );
+
+    assert!(matches!(
+        UcPack::default().deserialize_slice::<Reading>(&serialized),
+        Err(ucpack::UcPackError::NotSelfDescribing)
+    ));
+}
+
+#[test]
+fn test_tagged_unknown_frame() {
+    use serde::de::{self, EnumAccess, SeqAccess, VariantAccess};
+
+    /// Generic representation of any tagged value
+    #[derive(Debug, PartialEq)]
+    enum Dynamic {
+        Unit,
+        Bool(bool),
+        U8(u8),
+        I16(i16),
+        F32(f32),
+        Seq(Vec<Dynamic>),
+        Variant(u8, Box<Dynamic>),
+    }
+
+    impl<'de> Deserialize<'de> for Dynamic {
+        fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct DynamicVisitor;
+
+            impl<'de> de::Visitor<'de> for DynamicVisitor {
+                type Value = Dynamic;
+
+                fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    f.write_str("anything")
+                }
+
+                fn visit_unit<E>(self) -> Result<Dynamic, E> {
+                    Ok(Dynamic::Unit)
+                }
+
+                fn visit_bool<E>(self, v: bool) -> Result<Dynamic, E> {
+                    Ok(Dynamic::Bool(v))
+                }
+
+                fn visit_u8<E>(self, v: u8) -> Result<Dynamic, E> {
+                    Ok(Dynamic::U8(v))
+                }
+
+                fn visit_i16<E>(self, v: i16) -> Result<Dynamic, E> {
+                    Ok(Dynamic::I16(v))
+                }
+
+                fn visit_f32<E>(self, v: f32) -> Result<Dynamic, E> {
+                    Ok(Dynamic::F32(v))
+                }
+
+                fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Dynamic, A::Error> {
+                    let mut values = Vec::new();
+                    while let Some(value) = seq.next_element()? {
+                        values.push(value);
+                    }
+
+                    Ok(Dynamic::Seq(values))
+                }
+
+                fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Dynamic, A::Error> {
+                    let (idx, variant) = data.variant()?;
+                    let content = variant.newtype_variant()?;
+
+                    Ok(Dynamic::Variant(idx, Box::new(content)))
+                }
+            }
+
+            deserializer.deserialize_any(DynamicVisitor)
+        }
+    }
+
+    #[derive(Serialize)]
+    enum Command {
+        Stop,
+        Move { speed: i16, turn: f32 },
+    }
+
+    #[derive(Serialize)]
+    struct TestPayload {
+        id: u8,
+        enabled: bool,
+        commands: (Command, Command),
+    }
+
+    let ucpack = UcPack::default().with_tagged(true);
+    let serialized = ucpack
+        .serialize_vec(&TestPayload {
+            id: 7,
+            enabled: true,
+            commands: (
+                Command::Move {
+                    speed: -100,
+                    turn: 0.5,
+                },
+                Command::Stop,
+            ),
+        })
+        .unwrap();
+
+    let deserialized: Dynamic = ucpack.deserialize_slice(&serialized).unwrap();
+    assert_eq!(
+        deserialized,
+        Dynamic::Seq(vec![
+            Dynamic::U8(7),
+            Dynamic::Bool(true),
+            Dynamic::Seq(vec![
+                Dynamic::Variant(
+                    1,
+                    Box::new(Dynamic::Seq(vec![Dynamic::I16(-100), Dynamic::F32(0.5)]))
+                ),
+                Dynamic::Variant(0, Box::new(Dynamic::Unit)),
+            ]),
+        ])
+    );
+}
+
+#[test]
+fn test_deserialize_slice_consume() {
+    let ucpack = UcPack::default();
+
+    let first = ucpack.serialize_vec(&(1u8, 2u16)).unwrap();
+    let second = ucpack.serialize_vec(&3.0f32).unwrap();
+
+    let mut buffer = first.clone();
+    buffer.extend(&second[..4]); // second frame only partially received
+
+    let (value, consumed) = ucpack
+        .deserialize_slice_consume::<(u8, u16)>(&buffer)
+        .unwrap();
+    assert_eq!(value, (1, 2));
+    assert_eq!(consumed, first.len());
+
+    assert!(matches!(
+        ucpack.deserialize_slice_consume::<f32>(&buffer[consumed..]),
+        Err(ucpack::UcPackError::Eof { .. })
+    ));
+}
+
+#[test]
+fn test_deserialize_slice_consume_loop() {
+    let ucpack = UcPack::default();
+
+    let mut buffer = ucpack.serialize_vec(&(1u8, 2u16)).unwrap();
+    buffer.extend(ucpack.serialize_vec(&(3u8, 4u16)).unwrap());
+
+    let mut values = Vec::new();
+    let mut rest = &buffer[..];
+    while !rest.is_empty() {
+        let (value, consumed) = ucpack.deserialize_slice_consume::<(u8, u16)>(rest).unwrap();
+        values.push(value);
+        rest = &rest[consumed..];
+    }
+
+    assert_eq!(values, [(1, 2), (3, 4)]);
+}
+
+#[test]
+fn test_deserialize_slice_consume_len() {
+    use ucpack::checksum::{Checksum, Crc16};
+
+    fn check<C: Checksum>(ucpack: UcPack<C>, overhead: usize) {
+        let payload = (1u8, 2u16, 3.0f32);
+        let frame = ucpack.serialize_vec(&payload).unwrap();
+
+        let (value, consumed) = ucpack
+            .deserialize_slice_consume::<(u8, u16, f32)>(&frame)
+            .unwrap();
+        assert_eq!(value, payload);
+        assert_eq!(consumed, frame.len());
+        assert_eq!(consumed, 7 + overhead);
+    }
+
+    check(UcPack::default(), 4);
+    check(UcPack::default().with_extended_length(true), 5);
+    check(UcPack::default().with_checksum(Crc16::new()), 5);
+}
+
+#[test]
+fn test_unknown_variant() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum TestEnum {
+        Tag1(u8),
+        Tag2(u16),
+        Tag3(f32),
+    }
+
+    let ucpack = UcPack::default();
+
+    let serialized = ucpack.serialize_vec(&(7u8, 1u8)).unwrap();
+    assert!(matches!(
+        ucpack.deserialize_slice::<TestEnum>(&serialized),
+        Err(ucpack::UcPackError::UnknownVariant { index: 7 })
+    ));
+
+    let serialized = ucpack.serialize_vec(&(3u8, 1u8)).unwrap();
+    assert!(matches!(
+        ucpack.deserialize_slice::<TestEnum>(&serialized),
+        Err(ucpack::UcPackError::UnknownVariant { index: 3 })
+    ));
+
+    for payload in [TestEnum::Tag1(1), TestEnum::Tag2(2), TestEnum::Tag3(3.0)] {
+        let serialized = ucpack.serialize_vec(&payload).unwrap();
+        assert_eq!(
+            ucpack.deserialize_slice::<TestEnum>(&serialized).unwrap(),
+            payload
+        );
+    }
+}
+
+#[test]
+fn test_resync() {
+    let ucpack = UcPack::default();
+    let frame = ucpack.serialize_vec(&(b'A', 2u16)).unwrap();
+
+    // a frame which lost its first byte, followed by garbage and a valid frame
+    let mut buffer = frame[1..].to_vec();
+    buffer.extend(&[b'A', 0x02, b'A', 0xFF, 0x00]);
+    buffer.extend(&frame);
+
+    let offset = ucpack.resync(&buffer).unwrap();
+    assert_eq!(&buffer[offset..], frame);
+    assert_eq!(
+        ucpack
+            .deserialize_slice::<(u8, u16)>(&buffer[offset..])
+            .unwrap(),
+        (b'A', 2)
+    );
+
+    assert_eq!(ucpack.resync(&frame), Some(0));
+    assert_eq!(ucpack.resync(&frame[..frame.len() - 1]), None);
+    assert_eq!(ucpack.resync(&[]), None);
+}
+
+#[test]
+fn test_read_from() {
+    use std::io::Cursor;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct TestPayload {
+        a: u16,
+        b: f32,
+    }
+
+    let ucpack = UcPack::default();
+
+    let mut buffer = ucpack.serialize_vec(&TestPayload { a: 1, b: 2.0 }).unwrap();
+    buffer.extend(ucpack.serialize_vec(&TestPayload { a: 3, b: 4.0 }).unwrap());
+    buffer.pop(); // second frame is missing its crc
+
+    let mut reader = Cursor::new(buffer);
+    let first: TestPayload = ucpack.read_from(&mut reader).unwrap();
+    assert_eq!(first, TestPayload { a: 1, b: 2.0 });
+
+    assert!(matches!(
+        ucpack.read_from::<_, TestPayload>(&mut reader),
+        Err(ucpack::UcPackError::Eof { .. })
+    ));
+}
+
+#[test]
+fn test_tagged_enums() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum Plain {
+        Stop,
+        Go,
+    }
+
+    // unit and struct variants aren't supported, as serde
+    // deserializes their content through deserialize_any
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(tag = "t", content = "c")]
+    enum Adjacent {
+        Move(i16),
+        Turn(f32, bool),
+        Signal(Plain),
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(tag = "t")]
+    enum Internal {
+        Move { speed: i16 },
+    }
+
+    let payloads = [
+        Adjacent::Move(-3),
+        Adjacent::Turn(90.0, true),
+        Adjacent::Signal(Plain::Go),
+    ];
+
+    for ucpack in [UcPack::default(), UcPack::default().with_tagged(true)] {
+        for payload in &payloads {
+            let serialized = ucpack.serialize_vec(payload).unwrap();
+            let deserialized: Adjacent = ucpack.deserialize_slice(&serialized).unwrap();
+            assert_eq!(*payload, deserialized);
+        }
+    }
+
+    // adjacently tagged enums are encoded just like externally tagged ones
+    let ucpack = UcPack::default();
+    let serialized = ucpack.serialize_vec(&Adjacent::Signal(Plain::Go)).unwrap();
+    assert_eq!(&serialized[2..4], &[2, 1]);
+
+    let serialized = ucpack.serialize_vec(&(0u8, 10i16)).unwrap();
+    assert!(matches!(
+        ucpack.deserialize_slice::<Internal>(&serialized),
+        Err(ucpack::UcPackError::NotSelfDescribing)
+    ));
+}
+
+#[test]
+fn test_unit_variants() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum Plain {
+        Stop,
+        Go,
+    }
+
+    // the untagged format encodes unit variants as their bare index
+    let ucpack = UcPack::default();
+    let serialized = ucpack.serialize_vec(&Plain::Go).unwrap();
+    assert_eq!(&serialized[1..3], &[1, 1]);
+    assert_eq!(
+        ucpack.deserialize_slice::<Plain>(&serialized).unwrap(),
+        Plain::Go
+    );
+
+    let ucpack = ucpack.with_wide_variants(true);
+    let serialized = ucpack.serialize_vec(&Plain::Go).unwrap();
+    assert_eq!(&serialized[1..4], &[2, 1, 0]);
+    assert_eq!(
+        ucpack.deserialize_slice::<Plain>(&serialized).unwrap(),
+        Plain::Go
+    );
+}
+
+#[test]
+fn test_borrowed_bytes() {
+    fn as_bytes<S: serde::Serializer>(v: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(v)
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct TestPayload<'a> {
+        id: u8,
+        #[serde(serialize_with = "as_bytes")]
+        data: &'a [u8],
+        crc: u16,
+    }
+
+    let data = [0xDE, 0xAD, 0xBE, 0xEF];
+    let payload = TestPayload {
+        id: 1,
+        data: &data,
+        crc: 0xABCD,
+    };
+
+    for ucpack in [UcPack::default(), UcPack::default().with_tagged(true)] {
+        let serialized = ucpack.serialize_vec(&payload).unwrap();
+        let deserialized: TestPayload = ucpack.deserialize_slice(&serialized).unwrap();
+        assert_eq!(deserialized, payload);
+
+        // the deserialized slice points straight into the serialized frame
+        let offset = serialized
+            .windows(data.len())
+            .position(|window| window == data)
+            .unwrap();
+        assert!(std::ptr::eq(
+            deserialized.data.as_ptr(),
+            &serialized[offset]
+        ));
+    }
+
+    let serialized = UcPack::default().serialize_vec(&payload).unwrap();
+    assert_eq!(serialized[2..8], [1, 4, 0xDE, 0xAD, 0xBE, 0xEF]);
+}
+
+#[test]
+fn test_commands() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Joints {
+        left: f32,
+        right: f32,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Battery {
+        charge: u8,
+    }
+
+    let ucpack = UcPack::default();
+
+    let joints = Joints {
+        left: 1.0,
+        right: -1.0,
+    };
+    let battery = Battery { charge: 97 };
+
+    let frames = [
+        ucpack.serialize_command_vec(b'J', &joints).unwrap(),
+        ucpack.serialize_command_vec(b'B', &battery).unwrap(),
+    ];
+    assert_eq!(frames[0][2], b'J');
+    assert_eq!(frames[1][2], b'B');
+
+    for frame in &frames {
+        match ucpack.command_code(frame).unwrap() {
+            b'J' => assert_eq!(
+                ucpack.deserialize_command::<Joints>(frame, b'J').unwrap(),
+                joints
+            ),
+            b'B' => assert_eq!(
+                ucpack.deserialize_command::<Battery>(frame, b'B').unwrap(),
+                battery
+            ),
+            code => panic!("unexpected code {code}"),
+        }
+    }
+
+    let mut buffer = [0u8; 16];
+    let n = ucpack
+        .serialize_command_slice(b'B', &battery, &mut buffer)
+        .unwrap();
+    assert_eq!(&buffer[..n], frames[1]);
+
+    assert!(matches!(
+        ucpack.deserialize_command::<Battery>(&frames[1], b'X'),
+        Err(ucpack::UcPackError::WrongCode {
+            expected: b'X',
+            found: b'B'
+        })
+    ));
+}
+
+#[test]
+fn test_write_to() {
+    let ucpack = UcPack::default();
+    let payload = (1u8, -2i16, 3.0f32);
+
+    let mut writer = Vec::new();
+    ucpack.write_to(&payload, &mut writer).unwrap();
+    ucpack.write_to(&payload, &mut writer).unwrap();
+
+    let serialized = ucpack.serialize_vec(&payload).unwrap();
+    assert_eq!(writer, [serialized.clone(), serialized].concat());
+
+    let mut full = [0u8; 4];
+    let mut writer = &mut full[..];
+    assert!(matches!(
+        ucpack.write_to(&payload, &mut writer),
+        Err(ucpack::UcPackError::Io(_))
+    ));
+}
+
+#[test]
+fn test_borrowed_str() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct TestPayload<'a> {
+        id: u8,
+        name: &'a str,
+        owned: String,
+    }
+
+    let payload = TestPayload {
+        id: 3,
+        name: "alvik",
+        owned: "owned".to_string(),
+    };
+
+    for ucpack in [UcPack::default(), UcPack::default().with_tagged(true)] {
+        let serialized = ucpack.serialize_vec(&payload).unwrap();
+        let deserialized: TestPayload = ucpack.deserialize_slice(&serialized).unwrap();
+        assert_eq!(deserialized, payload);
+
+        let payload_offset = serialized
+            .windows(payload.name.len())
+            .position(|window| window == payload.name.as_bytes())
+            .unwrap();
+        assert!(core::ptr::eq(
+            deserialized.name.as_bytes().as_ptr(),
+            &serialized[payload_offset]
+        ));
+    }
+
+    let ucpack = UcPack::default();
+    // a 2 bytes long string, which isn't valid UTF-8
+    let serialized = ucpack.serialize_vec(&(1u8, 2u8, 0xFFu8, 0xFEu8)).unwrap();
+    let err = ucpack
+        .deserialize_slice::<(u8, &str)>(&serialized)
+        .unwrap_err();
+    assert!(matches!(err.inner(), ucpack::UcPackError::InvalidData));
+
+    let serialized = ucpack.serialize_vec(&(2u8, "\u{e8}")).unwrap();
+    assert_eq!(serialized[3..6], [2, 0xC3, 0xA8]);
+    assert!(matches!(
+        ucpack.deserialize_slice::<(u8, &str)>(&serialized[..]),
+        Ok((2, "\u{e8}"))
+    ));
+}
+
+#[test]
+fn test_wide_variants() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum TestEnum {
+        Tag1(u8),
+        Tag2(u16),
+    }
+
+    /// Stands in for variant 300 of a (very) big enum
+    #[derive(PartialEq, Debug)]
+    struct Variant300(u8);
+
+    impl Serialize for Variant300 {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_newtype_variant("Big", 300, "V300", &self.0)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Variant300 {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct Visitor;
+
+            impl<'de> serde::de::Visitor<'de> for Visitor {
+                type Value = Variant300;
+
+                fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    f.write_str("variant 300")
+                }
+
+                fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+                where
+                    A: serde::de::EnumAccess<'de>,
+                {
+                    use serde::de::{Error, VariantAccess};
+
+                    match data.variant::<u16>()? {
+                        (300, variant) => variant.newtype_variant().map(Variant300),
+                        (index, _) => Err(A::Error::custom(index)),
+                    }
+                }
+            }
+
+            const VARIANTS: &[&str] = &["V"; 301];
+            deserializer.deserialize_enum("Big", VARIANTS, Visitor)
+        }
+    }
+
+    for ucpack in [UcPack::default(), UcPack::default().with_tagged(true)] {
+        let ucpack = ucpack.with_wide_variants(true);
+
+        let serialized = ucpack.serialize_vec(&Variant300(7)).unwrap();
+        assert!(serialized.windows(2).any(|w| w == 300u16.to_le_bytes()));
+        assert_eq!(
+            ucpack.deserialize_slice::<Variant300>(&serialized).unwrap(),
+            Variant300(7)
+        );
+
+        let serialized = ucpack.serialize_vec(&TestEnum::Tag2(2)).unwrap();
+        assert_eq!(
+            ucpack.deserialize_slice::<TestEnum>(&serialized).unwrap(),
+            TestEnum::Tag2(2)
+        );
+    }
+
+    let ucpack = UcPack::default();
+    assert!(matches!(
+        ucpack.serialize_vec(&Variant300(7)),
+        Err(ucpack::UcPackError::BadVariant)
+    ));
+
+    let serialized = ucpack.serialize_vec(&TestEnum::Tag2(2)).unwrap();
+    assert_eq!(serialized[2..5], [1, 2, 0]);
+
+    let serialized = ucpack
+        .with_wide_variants(true)
+        .serialize_vec(&TestEnum::Tag2(2))
+        .unwrap();
+    assert_eq!(serialized[2..6], [1, 0, 2, 0]);
+}
+
+#[test]
+fn test_slice_cursor() {
+    use ucpack::{de::Deserializer, ser::Serializer, SliceCursor};
+
+    let mut buffer = [0u8; 8];
+    let mut cursor = SliceCursor::from_slice(&mut buffer[..]);
+    (1u8, -2i16, true)
+        .serialize(&mut Serializer::new(&mut cursor))
+        .unwrap();
+    assert_eq!(cursor.index(), 4);
+
+    let written = cursor.index();
+    let mut cursor = SliceCursor::from_slice(&buffer[..written]);
+    let value = <(u8, i16, bool)>::deserialize(&mut Deserializer::new(&mut cursor)).unwrap();
+    assert_eq!(value, (1, -2, true));
+    assert_eq!(cursor.index(), cursor.inner().len());
+}
+
+#[test]
+fn test_cursor_reuse() {
+    use ucpack::{ser::Serializer, SliceCursor, UcPackError};
+
+    fn fresh(payload: impl Serialize) -> Vec<u8> {
+        let mut cursor = SliceCursor::from_slice(vec![0u8; 8]);
+        payload
+            .serialize(&mut Serializer::new(&mut cursor))
+            .unwrap();
+        let n = cursor.index();
+        cursor.into_inner()[..n].to_vec()
+    }
+
+    // one cursor, reused for every payload
+    let mut buffer = [0u8; 8];
+    let mut cursor = SliceCursor::from_slice(&mut buffer[..]);
+
+    (1u8, 2u16)
+        .serialize(&mut Serializer::new(&mut cursor))
+        .unwrap();
+    assert_eq!(cursor.inner()[..cursor.index()], fresh((1u8, 2u16)));
+
+    cursor.reset();
+    (true, -3i16, 4u8)
+        .serialize(&mut Serializer::new(&mut cursor))
+        .unwrap();
+    assert_eq!(cursor.inner()[..cursor.index()], fresh((true, -3i16, 4u8)));
+
+    cursor.seek(8).unwrap();
+    assert!(matches!(
+        cursor.seek(10),
+        Err(UcPackError::BufferFull { needed: 2 })
+    ));
+    assert_eq!(cursor.index(), 8);
+}
+
+#[test]
+fn test_cursor() {
+    use ucpack::{
+        buffer::{cursor, WriteBuffer},
+        ser::Serializer,
+        UcPackError,
+    };
+
+    // hand made framing around the payload, straight into a stack array
+    let mut buffer = [0u8; 8];
+    let mut frame = cursor(&mut buffer);
+    frame.push_slice(&[b'A', 0]).unwrap();
+    (1u8, 2u16)
+        .serialize(&mut Serializer::new(&mut frame))
+        .unwrap();
+    let length = frame.bytes_written() - 2;
+    let crc = ucpack::crc8_slice(&frame.inner()[2..2 + length]);
+    frame.push_slice(&[b'#', crc]).unwrap();
+    let n = frame.bytes_written();
+    buffer[1] = length as u8;
+
+    assert_eq!(n, 7);
+    assert_eq!(
+        buffer[..n],
+        UcPack::default().serialize_vec(&(1u8, 2u16)).unwrap()
+    );
+
+    let mut small = [0u8; 1];
+    assert!(matches!(
+        1u16.serialize(&mut Serializer::new(cursor(&mut small))),
+        Err(UcPackError::BufferFull { .. })
+    ));
+}
+
+#[test]
+fn test_cobs_encoding() {
+    use ucpack::encoding::Encoding;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct TestPayload {
+        start: u8,
+        end: u8,
+        zero: u8,
+        both: u16,
+        markers: [u8; 8],
+        name: String,
+    }
+
+    let payload = TestPayload {
+        start: b'A',
+        end: b'#',
+        zero: 0,
+        both: u16::from_le_bytes([b'A', b'#']),
+        markers: [b'A', b'#', 0, b'A', b'A', 0, 0, b'#'],
+        name: "A#A#".to_string(),
+    };
+
+    let ucpack = UcPack::default().with_encoding(Encoding::Cobs);
+    let serialized = ucpack.serialize_vec(&payload).unwrap();
+
+    let [b'A', len, encoded @ .., 0x00, _crc] = &serialized[..] else {
+        panic!("unexpected frame {serialized:?}");
+    };
+    assert_eq!(usize::from(*len), encoded.len());
+    assert!(!encoded.contains(&0));
+    assert_eq!(ucpack.serialize_size(&payload).unwrap(), serialized.len());
+
+    let mut buffer = [0u8; 64];
+    let n = ucpack.serialize_slice(&payload, &mut buffer).unwrap();
+    assert_eq!(buffer[..n], serialized[..]);
+
+    assert_eq!(
+        ucpack
+            .deserialize_slice::<TestPayload>(&serialized)
+            .unwrap(),
+        payload
+    );
+
+    // the raw flavor can't read it
+    assert!(UcPack::default()
+        .deserialize_slice::<TestPayload>(&serialized)
+        .is_err());
+
+    // a stream with leading garbage full of markers still resyncs
+    let stream = [&[b'A', b'#', 0, b'A'][..], &serialized].concat();
+    let offset = ucpack.resync(&stream).unwrap();
+    assert_eq!(
+        ucpack
+            .deserialize_slice::<TestPayload>(&stream[offset..])
+            .unwrap(),
+        payload
+    );
+
+    // borrowed data can't point into the decoded payload
+    let serialized = ucpack.serialize_vec(&(1u8, "borrowed")).unwrap();
+    assert!(ucpack.deserialize_slice::<(u8, &str)>(&serialized).is_err());
+}
+
+#[test]
+fn test_decoder() {
+    use ucpack::decoder::Decoder;
+
+    let ucpack = UcPack::default();
+    let mut decoder = Decoder::<32>::new(ucpack);
+
+    let first = ucpack.serialize_vec(&(1u8, -2i16)).unwrap();
+    let mut corrupted = ucpack.serialize_vec(&(3u8, -4i16)).unwrap();
+    *corrupted.last_mut().unwrap() ^= 0xFF;
+    let second = ucpack.serialize_vec(&(5u8, -6i16)).unwrap();
+
+    let stream = [&b"\x00#garbage"[..], &first, &corrupted, b"\xFF", &second].concat();
+
+    let mut received = Vec::new();
+    for byte in stream {
+        if let Some(packet) = decoder.push(byte) {
+            received.push(ucpack.deserialize_slice::<(u8, i16)>(packet).unwrap());
+        }
+    }
+
+    assert_eq!(received, [(1, -2), (5, -6)]);
+    assert!(decoder.is_empty());
+
+    // a frame not fitting in the buffer is dropped
+    let mut decoder = Decoder::<8>::new(ucpack);
+    let big = ucpack.serialize_vec(&(1u8, 2u16, 3u16, 4u16)).unwrap();
+    assert!(big.iter().all(|&byte| decoder.push(byte).is_none()));
+
+    for &byte in &first[..3] {
+        decoder.push(byte);
+    }
+    assert_eq!(decoder.len(), 3);
+    decoder.reset();
+    let completed = first.iter().filter(|&&byte| decoder.push(byte).is_some());
+    assert_eq!(completed.count(), 1);
+
+    // garbage equal to the start index doesn't swallow the frame following it
+    for garbage in [&b"A"[..], b"A\x03", b"A\x01\x00"] {
+        let mut decoder = Decoder::<32>::new(ucpack);
+        let stream = [garbage, &first, &second].concat();
+
+        let received: Vec<_> = stream
+            .iter()
+            .filter_map(|&byte| decoder.push(byte).map(<[u8]>::to_vec))
+            .collect();
+        assert_eq!(received, [first.clone(), second.clone()]);
+    }
+}
+
+#[test]
+fn test_decoder_custom_indices() {
+    use ucpack::decoder::StreamDecoder;
+
+    let ucpack = UcPack::new(b'S', b'E');
+    let mut decoder = StreamDecoder::<16>::new(ucpack);
+
+    let frame = ucpack.serialize_vec(&(1u8, 2u16)).unwrap();
+    let mut wrong_end = frame.clone();
+    wrong_end[5] = b'#';
+
+    // default indices are just garbage, as is the frame with the wrong end index
+    let default = UcPack::default().serialize_vec(&(3u8, 4u16)).unwrap();
+    let stream = [&default[..], &wrong_end, &frame].concat();
+
+    let packets: Vec<_> = stream
+        .iter()
+        .filter_map(|&byte| decoder.feed(byte).map(<[u8]>::to_vec))
+        .collect();
+    assert_eq!(packets, [frame]);
+
+    for &byte in &wrong_end[..3] {
+        decoder.feed(byte);
+    }
+    decoder.reset();
+    assert!(decoder.is_empty());
+}
+
+#[test]
+fn test_packet_framer() {
+    use ucpack::decoder::PacketFramer;
+
+    let ucpack = UcPack::default();
+
+    let first = ucpack.serialize_vec(&(1u8, -2i16)).unwrap();
+    let mut corrupted = ucpack.serialize_vec(&(3u8, -4i16)).unwrap();
+    *corrupted.last_mut().unwrap() ^= 0xFF;
+    let second = ucpack.serialize_vec(&(5u8, -6i16)).unwrap();
+    // doesn't fit in the framer's buffer
+    let big = ucpack.serialize_vec(&[7u16; 8]).unwrap();
+    let third = ucpack.serialize_vec(&(9u8, -10i16)).unwrap();
+
+    let stream = [
+        &b"\x00#garbage"[..],
+        &first,
+        &corrupted,
+        b"\xFF",
+        &second,
+        &big,
+        &third,
+    ]
+    .concat();
+
+    for chunk_size in [1, 2, 5, 16, stream.len()] {
+        let mut framer = PacketFramer::<16>::new(ucpack);
+        let mut received = Vec::new();
+
+        for chunk in stream.chunks(chunk_size) {
+            let mut packets = framer.feed_slice(chunk);
+            while let Some(packet) = packets.next_packet() {
+                received.push(ucpack.deserialize_slice::<(u8, i16)>(packet).unwrap());
+            }
+        }
+
+        assert_eq!(received, [(1, -2), (5, -6), (9, -10)]);
+        assert_eq!(framer.corrupt_count(), 1);
+        assert!(framer.is_empty());
+    }
+
+    // an incomplete frame is kept for the next chunk
+    let mut framer = PacketFramer::<16>::new(ucpack);
+    assert!(framer.feed_slice(&first[..3]).next_packet().is_none());
+    assert_eq!(framer.len(), 3);
+    framer.reset();
+    assert!(framer.feed_slice(&first[3..]).next_packet().is_none());
+    assert!(framer.is_empty());
+}
+
+#[test]
+fn test_escaped_encoding() {
+    use ucpack::encoding::Encoding;
+
+    let ucpack = UcPack::default().with_encoding(Encoding::Escaped { escape: 0x7D });
+    let payload = (b'A', b'#', 0x7Du8, u16::from_le_bytes([b'#', b'A']), 1u8);
+
+    let serialized = ucpack.serialize_vec(&payload).unwrap();
+    assert_eq!(serialized[1], 11); // 5 escaped bytes taking 2 bytes each + 1 untouched byte
+    assert!(!serialized[2..serialized.len() - 2]
+        .iter()
+        .any(|byte| [b'A', b'#'].contains(byte)));
+    assert_eq!(ucpack.serialize_size(&payload).unwrap(), serialized.len());
+
+    assert_eq!(
+        ucpack
+            .deserialize_slice::<(u8, u8, u8, u16, u8)>(&serialized)
+            .unwrap(),
+        payload
+    );
+
+    // a truncated frame followed by a whole one: the only start index
+    // left after the truncated one is the one of the whole frame
+    let stream = [&serialized[..6], &serialized].concat();
+    assert_eq!(ucpack.resync(&stream[1..]), Some(5));
+}
+
+#[test]
+fn test_escaped_encoding_only_indices() {
+    use ucpack::encoding::Encoding;
+
+    let ucpack = UcPack::default().with_encoding(Encoding::Escaped { escape: 0x7D });
+    let payload = ([b'A'; 32], [b'#'; 32], [0x7Du8; 32]);
+
+    let mut buffer = [0u8; 200];
+    let n = ucpack.serialize_slice(&payload, &mut buffer).unwrap();
+    assert_eq!(n, 2 + 192 + 2);
+    assert_eq!(buffer[..n], ucpack.serialize_vec(&payload).unwrap());
+    assert!(!buffer[1..n - 2].contains(&b'A'));
+
+    let frame = ucpack.is_complete_message(&buffer[..n]).unwrap();
+    assert_eq!(
+        ucpack
+            .deserialize_slice::<([u8; 32], [u8; 32], [u8; 32])>(frame)
+            .unwrap(),
+        payload
+    );
+
+    // the raw payload fits, but not once escaped
+    let too_long = ([b'A'; 32], [b'#'; 32], [0x7Du8; 32], [b'A'; 32]);
+    assert!(ucpack.serialize_vec(&too_long).is_err());
+    let extended = ucpack.with_extended_length(true);
+    let serialized = extended.serialize_vec(&too_long).unwrap();
+    assert_eq!(
+        extended
+            .deserialize_slice::<([u8; 32], [u8; 32], [u8; 32], [u8; 32])>(&serialized)
+            .unwrap(),
+        too_long
+    );
+}
+
+#[test]
+fn test_escaped_encoding_raw_length_and_crc() {
+    use ucpack::encoding::Encoding;
+
+    let ucpack = UcPack::default().with_encoding(Encoding::Escaped { escape: 0x7D });
+
+    // a 65 bytes long payload: its length byte is the start index
+    let payload = ([1u8; 32], [2u8; 32], 3u8);
+    let serialized = ucpack.serialize_vec(&payload).unwrap();
+    let n = serialized.len();
+    assert_eq!(serialized[1], b'A');
+    assert!(!serialized[2..n - 2].contains(&b'A'));
+    assert_eq!(
+        ucpack
+            .deserialize_slice::<([u8; 32], [u8; 32], u8)>(&serialized)
+            .unwrap(),
+        payload
+    );
+
+    // resyncing on the length byte is rejected, landing on the next frame
+    let stream = [&serialized[1..], &serialized].concat();
+    assert_eq!(ucpack.resync(&stream), Some(n - 1));
+
+    // a payload whose crc is the start index
+    let (value, serialized) = (0..=u16::MAX)
+        .map(|value| (value, ucpack.serialize_vec(&value).unwrap()))
+        .find(|(_, frame)| frame.last() == Some(&b'A'))
+        .unwrap();
+    let n = serialized.len();
+    assert!(!serialized[2..n - 2].contains(&b'A'));
+    assert_eq!(ucpack.deserialize_slice::<u16>(&serialized).unwrap(), value);
+
+    let stream = [&serialized[n - 1..], &serialized].concat();
+    assert_eq!(ucpack.resync(&stream), Some(1));
+}
+
+#[test]
+#[should_panic(expected = "escape byte must differ")]
+fn test_escaped_encoding_colliding_escape() {
+    use ucpack::encoding::Encoding;
+
+    let _ = UcPack::default().with_encoding(Encoding::Escaped { escape: b'#' });
+}
+
+#[test]
+#[should_panic(expected = "escape byte must differ")]
+fn test_builder_colliding_escape() {
+    use ucpack::encoding::Encoding;
+
+    // the escape byte is only checked against the final indices
+    let _ = UcPack::builder()
+        .encoding(Encoding::Escaped { escape: b'B' })
+        .start(b'B')
+        .build();
+}
+
+#[test]
+fn test_find_frame() {
+    let ucpack = UcPack::default();
+    let frame = ucpack.serialize_vec(&(b'A', 2u16)).unwrap();
+
+    // garbage prefix
+    let buffer = [&b"\x00\xFF#"[..], &frame].concat();
+    assert_eq!(ucpack.find_frame(&buffer), Some((3, &frame[..])));
+
+    // start index in the garbage, looking like the start of a frame
+    let buffer = [&b"A\x01A"[..], &frame, b"trailing"].concat();
+    let (offset, found) = ucpack.find_frame(&buffer).unwrap();
+    assert_eq!(offset, 3);
+    assert_eq!(
+        ucpack.deserialize_slice::<(u8, u16)>(found).unwrap(),
+        (b'A', 2)
+    );
+
+    // start index inside the payload of a truncated frame
+    let buffer = [&frame[..4], &frame].concat();
+    assert_eq!(ucpack.find_frame(&buffer), Some((4, &frame[..])));
+
+    // no valid frame
+    let mut corrupted = frame.clone();
+    corrupted[3] ^= 0xFF;
+    assert_eq!(ucpack.find_frame(b"AAAA"), None);
+    assert_eq!(ucpack.find_frame(&corrupted), None);
+    assert_eq!(ucpack.find_frame(&[]), None);
+}
+
+#[test]
+fn test_accumulator() {
+    use ucpack::decoder::Accumulator;
+
+    let ucpack = UcPack::default();
+    let frame = ucpack.serialize_vec(&(1u8, 2u16, 3.0f32)).unwrap();
+
+    let mut accumulator = Accumulator::<16>::new(b'A');
+    for &byte in b"\x00\x01#" {
+        assert!(accumulator.push(byte).is_none());
+        assert!(accumulator.is_empty());
+    }
+
+    for (i, &byte) in frame.iter().enumerate() {
+        match accumulator.push(byte) {
+            Some(complete) => {
+                assert_eq!(i, frame.len() - 1);
+                assert_eq!(complete, frame);
+            }
+            None => assert_eq!(accumulator.len(), i + 1),
+        }
+    }
+    assert!(accumulator.is_empty());
+
+    // frames aren't verified
+    let mut corrupted = frame.clone();
+    *corrupted.last_mut().unwrap() ^= 0xFF;
+    assert_eq!(
+        corrupted
+            .iter()
+            .filter_map(|&byte| accumulator.push(byte).map(<[u8]>::to_vec))
+            .last(),
+        Some(corrupted.clone())
+    );
+
+    // overflowing frames are dropped
+    let mut accumulator = Accumulator::<8>::new(b'A');
+    assert!(frame.iter().all(|&byte| accumulator.push(byte).is_none()));
+}
+
+#[test]
+fn test_is_complete_message_method() {
+    use ucpack::UcPackError;
+
+    let ucpack = UcPack::default();
+    let frame = ucpack.serialize_vec(&(1u8, 2u16)).unwrap();
+
+    assert_eq!(ucpack.is_complete_message(&frame).unwrap(), &frame[..]);
+
+    let stream = [&frame[..], &frame[..3]].concat();
+    assert_eq!(ucpack.is_complete_message(&stream).unwrap(), &frame[..]);
+
+    for partial in [&frame[..0], &frame[..1], &frame[..frame.len() - 1]] {
+        assert!(matches!(
+            ucpack.is_complete_message(partial),
+            Err(UcPackError::Eof { .. })
+        ));
+    }
+
+    // starting mid-frame: the free function trusts the garbage length
+    assert!(ucpack::is_complete_message(&frame[1..]).is_some());
+    assert!(matches!(
+        ucpack.is_complete_message(&frame[1..]),
+        Err(UcPackError::WrongIndex { .. })
+    ));
+
+    // right start index, but the length doesn't lead to the end index
+    let mut wrong_length = frame.clone();
+    wrong_length[1] -= 1;
+    assert!(matches!(
+        ucpack.is_complete_message(&wrong_length),
+        Err(UcPackError::WrongIndex { .. })
+    ));
+}
+
+#[test]
+fn test_check_message() {
+    use ucpack::UcPackError;
+
+    let ucpack = UcPack::default();
+    let frame = ucpack.serialize_vec(&(1u8, 2u16)).unwrap();
+    assert_eq!(ucpack.check_message(&frame).unwrap(), &frame[..]);
+
+    let mut corrupted = frame.clone();
+    corrupted[2] ^= 0xFF;
+    assert!(matches!(
+        ucpack.check_message(&corrupted),
+        Err(UcPackError::WrongCrc { .. })
+    ));
+    // the lenient function doesn't notice
+    assert!(ucpack::is_complete_message(&corrupted).is_some());
+
+    assert!(matches!(
+        ucpack.check_message(&frame[..4]),
+        Err(UcPackError::Eof { .. })
+    ));
+    assert!(matches!(
+        ucpack.check_message(&frame[1..]),
+        Err(UcPackError::WrongIndex { .. })
+    ));
+}
+
+#[test]
+fn test_frames() {
+    let ucpack = UcPack::default();
+    let frames: Vec<Vec<u8>> = (1u8..=3)
+        .map(|i| ucpack.serialize_vec(&(i, u16::from(i) * 1000)).unwrap())
+        .collect();
+
+    // back-to-back
+    let buffer = frames.concat();
+    let mut iter = ucpack.frames(&buffer);
+    assert_eq!(iter.by_ref().collect::<Vec<_>>(), frames);
+    assert_eq!(iter.consumed(), buffer.len());
+    assert!(iter.remaining().is_empty());
+
+    // separated by noise, including a corrupted frame and stray start indices
+    let mut corrupted = frames[1].clone();
+    corrupted[2] ^= 0xFF;
+    let buffer = [
+        &b"\x00AA"[..],
+        &frames[0],
+        b"#noise",
+        &corrupted,
+        &frames[1],
+        b"A\xFF",
+        &frames[2],
+    ]
+    .concat();
+    assert_eq!(ucpack.frames(&buffer).collect::<Vec<_>>(), frames);
+
+    // trailing half-frame preserved
+    let buffer = [&frames[0][..], b"garbage", &frames[1], &frames[2][..4]].concat();
+    let mut iter = ucpack.frames(&buffer);
+    assert_eq!(iter.next(), Some(&frames[0][..]));
+    assert_eq!(iter.next(), Some(&frames[1][..]));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.remaining(), &frames[2][..4]);
+    assert_eq!(iter.consumed(), buffer.len() - 4);
+
+    // the rest of the frame arrives
+    let buffer = [iter.remaining(), &frames[2][4..]].concat();
+    assert_eq!(ucpack.frames(&buffer).collect::<Vec<_>>(), [&frames[2]]);
+}
+
+#[test]
+fn test_extended_length() {
+    use ucpack::{decoder::Decoder, UcPackError};
+
+    let table: [[u16; 30]; 10] =
+        core::array::from_fn(|i| core::array::from_fn(|j| (i * 30 + j) as u16));
+
+    let classic = UcPack::default();
+    assert!(matches!(
+        classic.serialize_vec(&table),
+        Err(UcPackError::TooLong)
+    ));
+    assert!(matches!(
+        classic.serialize_size(&table),
+        Err(UcPackError::TooLong)
+    ));
+
+    let extended = UcPack::new(b'E', b'#').with_extended_length(true);
+    let serialized = extended.serialize_vec(&table).unwrap();
+    assert_eq!(serialized.len(), 600 + 5);
+    assert_eq!(serialized[..3], [b'E', 0x58, 0x02]);
+    assert_eq!(extended.serialize_size(&table).unwrap(), serialized.len());
+
+    let mut buffer = vec![0u8; 605];
+    assert_eq!(extended.serialize_slice(&table, &mut buffer).unwrap(), 605);
+    assert_eq!(buffer, serialized);
+
+    assert_eq!(
+        extended
+            .deserialize_slice::<[[u16; 30]; 10]>(&serialized)
+            .unwrap(),
+        table
+    );
+    assert!(matches!(
+        extended.deserialize_slice::<[[u16; 30]; 10]>(&serialized[..604]),
+        Err(UcPackError::Eof { .. })
+    ));
+    assert_eq!(
+        extended
+            .read_from::<_, [[u16; 30]; 10]>(&mut &serialized[..])
+            .unwrap(),
+        table
+    );
+
+    // small payloads work too, and can't be mistaken for classic frames
+    let small = extended.serialize_vec(&(1u8, 2u16)).unwrap();
+    assert_eq!(small, [b'E', 3, 0, 1, 2, 0, b'#', small[7]]);
+    assert_eq!(
+        extended.deserialize_slice::<(u8, u16)>(&small).unwrap(),
+        (1, 2)
+    );
+    assert!(classic.deserialize_slice::<(u8, u16)>(&small).is_err());
+    assert!(extended
+        .deserialize_slice::<(u8, u16)>(&classic.serialize_vec(&(1u8, 2u16)).unwrap())
+        .is_err());
+
+    let stream = [&b"\x00E"[..], &small, &serialized].concat();
+    assert_eq!(extended.frames(&stream).count(), 2);
+
+    let stream = [&b"\x00"[..], &small, &serialized].concat();
+    let mut decoder = Decoder::<1024>::new(extended);
+    let decoded = stream
+        .iter()
+        .filter_map(|&byte| decoder.push(byte).map(<[u8]>::len))
+        .collect::<Vec<_>>();
+    assert_eq!(decoded, [small.len(), serialized.len()]);
+}
+
+#[test]
+fn test_write_buffer_capacity() {
+    use ucpack::buffer::WriteBuffer;
+
+    let ucpack = UcPack::default();
+    let payload = (1u8, 2u16, 3.0f32);
+    let required = ucpack.serialize_size(&payload).unwrap();
+
+    let mut small = [0u8; 8];
+    let cursor = ucpack::SliceCursor::from_slice(&mut small[..]);
+    assert!(cursor.capacity().is_some_and(|cap| required > cap));
+
+    // not to be confused with Vec::capacity
+    assert_eq!(WriteBuffer::capacity(&Vec::<u8>::new()), None);
+    assert_eq!(vec![1u8, 2].remaining_capacity(), usize::MAX);
+}
+
+#[test]
+fn test_crc16_checksum() {
+    use ucpack::{checksum::Crc16, decoder::Decoder, UcPackError};
+
+    let ucpack = UcPack::default().with_checksum(Crc16::new());
+
+    let serialized = ucpack.serialize_vec(&(1u8, 2u16)).unwrap();
+    // CRC-16/CCITT-FALSE of [1, 2, 0]
+    assert_eq!(serialized, [b'A', 3, 1, 2, 0, b'#', 0x9D, 0xCE]);
+    assert_eq!(ucpack.serialize_size(&(1u8, 2u16)).unwrap(), 8);
+
+    let mut buffer = [0u8; 8];
+    assert_eq!(
+        ucpack.serialize_slice(&(1u8, 2u16), &mut buffer).unwrap(),
+        8
+    );
+    assert_eq!(buffer, serialized[..]);
+
+    assert_eq!(
+        ucpack.deserialize_slice::<(u8, u16)>(&serialized).unwrap(),
+        (1, 2)
+    );
+    assert!(matches!(
+        ucpack.deserialize_slice::<(u8, u16)>(&serialized[..7]),
+        Err(UcPackError::Eof { .. })
+    ));
+    assert_eq!(ucpack.is_complete_message(&serialized).unwrap(), serialized);
+
+    let mut corrupted = serialized.clone();
+    corrupted[7] ^= 0x01;
+    assert!(matches!(
+        ucpack.deserialize_slice::<(u8, u16)>(&corrupted),
+        Err(UcPackError::WrongCrc { .. })
+    ));
+
+    // classic frames aren't mistaken for crc16 ones
+    let classic = UcPack::default().serialize_vec(&(1u8, 2u16)).unwrap();
+    assert!(ucpack.is_complete_message(&classic).is_err());
+
+    let mut decoder = Decoder::<16, _>::new(ucpack);
+    let decoded = serialized
+        .iter()
+        .find_map(|&byte| decoder.push(byte).map(<[u8]>::len));
+    assert_eq!(decoded, Some(serialized.len()));
+}
+
+#[test]
+fn test_custom_checksum() {
+    use ucpack::{checksum::Checksum, UcPackError};
+
+    /// Legacy boards XOR all the payload bytes together
+    #[derive(Clone, Copy, Default)]
+    struct Xor(u8);
+
+    impl Checksum for Xor {
+        type Output = [u8; 1];
+        const SIZE: usize = 1;
+
+        fn update(&mut self, data: &[u8]) {
+            self.0 = data.iter().fold(self.0, |xor, byte| xor ^ byte);
+        }
+
+        fn finalize(&self) -> [u8; 1] {
+            [self.0]
+        }
+    }
+
+    let ucpack = UcPack::default().with_checksum(Xor::default());
+    let payload = (0x0Fu8, 0x1234u16, true);
+
+    let serialized = ucpack.serialize_vec(&payload).unwrap();
+    assert_eq!(
+        serialized,
+        [b'A', 4, 0x0F, 0x34, 0x12, 1, b'#', 0x0F ^ 0x34 ^ 0x12 ^ 1]
+    );
+    assert_eq!(ucpack.serialize_size(&payload).unwrap(), serialized.len());
+    assert_eq!(
+        ucpack
+            .deserialize_slice::<(u8, u16, bool)>(&serialized)
+            .unwrap(),
+        payload
+    );
+
+    let mut corrupted = serialized.clone();
+    corrupted[2] ^= 0x01;
+    assert!(matches!(
+        ucpack.deserialize_slice::<(u8, u16, bool)>(&corrupted),
+        Err(UcPackError::WrongCrc { .. })
+    ));
+    assert!(UcPack::default()
+        .deserialize_slice::<(u8, u16, bool)>(&serialized)
+        .is_err());
+}
+
+#[test]
+fn test_crc8_config() {
+    use ucpack::{
+        checksum::{Crc8, Crc8Config},
+        UcPackError,
+    };
+
+    let ucpack = UcPack::default().with_checksum(Crc8::with_config(Crc8Config::SMBUS));
+
+    let serialized = ucpack.serialize_vec(&(1u8, 2u16)).unwrap();
+    let crc = ucpack::crc8_slice_with(Crc8Config::SMBUS, &serialized[2..5]);
+    assert_eq!(serialized, [b'A', 3, 1, 2, 0, b'#', crc]);
+    assert_ne!(crc, ucpack::crc8_slice(&serialized[2..5]));
+
+    assert_eq!(
+        ucpack.deserialize_slice::<(u8, u16)>(&serialized).unwrap(),
+        (1, 2)
+    );
+    assert!(matches!(
+        UcPack::default().deserialize_slice::<(u8, u16)>(&serialized),
+        Err(UcPackError::WrongCrc { .. })
+    ));
+}
+
+#[test]
+fn test_error_position() {
+    use ucpack::UcPackError;
+
+    let ucpack = UcPack::default();
+
+    // a valid frame, carrying a payload shorter than expected
+    let serialized = ucpack.serialize_vec(&(1u8, 2u16)).unwrap();
+    let err = ucpack
+        .deserialize_slice::<(u8, u16, f32)>(&serialized)
+        .unwrap_err();
+    assert!(matches!(err.inner(), UcPackError::Eof { needed: 4 }));
+    assert_eq!(err.position(), Some(3));
+    assert_eq!(
+        err.to_string(),
+        "not enough data to deserialize (4 more bytes needed) at payload byte 3"
+    );
+
+    let serialized = ucpack.serialize_vec(&(1u8, 2u8)).unwrap();
+    let err = ucpack
+        .deserialize_slice::<(u8, bool)>(&serialized)
+        .unwrap_err();
+    assert!(matches!(err.inner(), UcPackError::InvalidData));
+    assert_eq!(err.position(), Some(2));
+
+    // framing errors aren't tied to the payload
+    let err = ucpack
+        .deserialize_slice::<(u8, u8)>(&serialized[..4])
+        .unwrap_err();
+    assert!(matches!(err, UcPackError::Eof { needed: 2 }));
+    assert_eq!(err.position(), None);
+}
+
+#[test]
+fn test_framing_error_details() {
+    use ucpack::{IndexKind, UcPackError};
+
+    let ucpack = UcPack::default();
+    let frame = ucpack.serialize_vec(&(1u8, 2u16)).unwrap();
+
+    // truncated frames tell how much is missing, header included
+    for (len, missing) in [(1, 1), (2, 5), (5, 2), (6, 1)] {
+        assert!(matches!(
+            ucpack.deserialize_slice::<(u8, u16)>(&frame[..len]),
+            Err(UcPackError::Eof { needed }) if needed == missing
+        ));
+    }
+
+    let mut wrong_stop = frame.clone();
+    wrong_stop[5] = b'$';
+    let err = ucpack
+        .deserialize_slice::<(u8, u16)>(&wrong_stop)
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        UcPackError::WrongIndex {
+            expected: b'#',
+            found: b'$',
+            position: IndexKind::End,
+        }
+    ));
+    assert_eq!(err.to_string(), "expected end index 0x23, found 0x24");
+
+    assert!(matches!(
+        ucpack.is_complete_message(&frame[1..]),
+        Err(UcPackError::WrongIndex {
+            expected: b'A',
+            found: 3,
+            position: IndexKind::Start,
+        })
+    ));
+}
+
+#[test]
+fn test_dyn_buffers() {
+    use ucpack::{
+        buffer::{dynamic::DynReadBuffer, RingBuffer, SliceCursor, WriteBuffer},
+        de::Deserializer,
+        ser::Serializer,
+    };
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Reading<'a> {
+        id: u8,
+        value: f32,
+        label: &'a str,
+    }
+
+    let reading = Reading {
+        id: 4,
+        value: 1.5,
+        label: "temp",
+    };
+
+    // the sink is only picked at runtime
+    let mut vec = Vec::new();
+    let mut ring = RingBuffer::<16>::new();
+    for sink in [&mut vec as &mut dyn WriteBuffer, &mut ring] {
+        reading.serialize(&mut Serializer::new(sink)).unwrap();
+    }
+    assert_eq!(vec.len(), ring.bytes_written());
+
+    // and so is the source, which still lends out borrowed data
+    let mut slice = SliceCursor::from_slice(&vec[..]);
+    let mut io = std::io::Cursor::new(vec.clone());
+    let sources = [&mut slice as &mut dyn DynReadBuffer, &mut io];
+
+    let borrowed = Reading::deserialize(&mut Deserializer::new(&mut *sources[0])).unwrap();
+    assert_eq!(borrowed, reading);
+    assert_eq!(sources[0].remaining_bytes(), 0);
+
+    // a source which can't lend its data out can't produce a &str
+    Reading::deserialize(&mut Deserializer::new(&mut *sources[1])).unwrap_err();
+}
+
+#[test]
+fn test_deserialize_unchecked() {
+    use ucpack::UcPackError;
+
+    let ucpack = UcPack::default();
+    let mut frame = ucpack.serialize_vec(&(1u8, 0x0203u16)).unwrap();
+    let crc = frame.last_mut().unwrap();
+    *crc = !*crc;
+
+    assert!(matches!(
+        ucpack.deserialize_slice::<(u8, u16)>(&frame),
+        Err(UcPackError::WrongCrc { .. })
+    ));
+    assert_eq!(
+        ucpack
+            .deserialize_slice_unchecked::<(u8, u16)>(&frame)
+            .unwrap(),
+        (1, 0x0203)
+    );
+
+    // indexes aren't checked either, but the frame must be complete
+    frame[0] = b'B';
+    ucpack
+        .deserialize_slice_unchecked::<(u8, u16)>(&frame)
+        .unwrap();
+    assert!(matches!(
+        ucpack.deserialize_slice_unchecked::<(u8, u16)>(&frame[..frame.len() - 1]),
+        Err(UcPackError::Eof { .. })
+    ));
+}
+
+#[cfg(feature = "defmt")]
+#[test]
+fn test_defmt_format() {
+    fn assert_format<T: defmt::Format>(_: &T) {}
+
+    assert_format(&ucpack::UcPackError::NoSupport("i64"));
+}
+
+#[test]
+fn test_deserialize_read() {
+    use ucpack::{buffer::SliceCursor, UcPackError};
+
+    let ucpack = UcPack::default();
+    let mut stream = ucpack.serialize_vec(&(1u8, 2u16)).unwrap();
+    stream.extend(ucpack.serialize_vec(&3.0f32).unwrap());
+
+    let mut cursor = SliceCursor::from_slice(&stream[..]);
+    let first: (u8, u16) = ucpack.deserialize_read(&mut cursor).unwrap();
+    let second: f32 = ucpack.deserialize_read(&mut cursor).unwrap();
+    assert_eq!((first, second), ((1, 2), 3.0));
+
+    assert!(matches!(
+        ucpack.deserialize_read::<_, f32>(&mut cursor),
+        Err(UcPackError::Eof { .. })
+    ));
+
+    // too big for the stack buffer
+    let ucpack = UcPack::default().with_extended_length(true);
+    let frame = ucpack.serialize_vec(&[[0u8; 30]; 10]).unwrap();
+    assert!(matches!(
+        ucpack.deserialize_read::<_, [[u8; 30]; 10]>(SliceCursor::from_slice(&frame[..])),
+        Err(UcPackError::TooLong)
+    ));
+}
+
+#[cfg(feature = "cobs")]
+#[test]
+fn test_cobs_framing() {
+    use ucpack::{checksum::Crc16, decoder::CobsDecoder, UcPackError};
+
+    let ucpack = UcPack::default();
+
+    // length 4, payload 00 00 11 00, crc 0x28: the zeros are replaced by
+    // the distance to the next one, as the reference algorithm does
+    let serialized = ucpack.serialize_cobs_vec(&(0u8, 0x1100u16, 0u8)).unwrap();
+    assert_eq!(serialized, [0x02, 0x04, 0x01, 0x02, 0x11, 0x02, 0x28, 0x00]);
+    assert_eq!(
+        ucpack
+            .deserialize_cobs_slice::<(u8, u16, u8)>(&serialized)
+            .unwrap(),
+        (0, 0x1100, 0)
+    );
+
+    let zeros = [[0u8; 32]; 6];
+    for ucpack in [ucpack, ucpack.with_extended_length(true)] {
+        let mut buffer = [0xFFu8; 256];
+        let n = ucpack.serialize_cobs_slice(&zeros, &mut buffer).unwrap();
+        assert_eq!(buffer[..n], ucpack.serialize_cobs_vec(&zeros).unwrap());
+        assert_eq!(buffer[..n].iter().position(|&byte| byte == 0), Some(n - 1));
+        assert_eq!(
+            ucpack
+                .deserialize_cobs_slice::<[[u8; 32]; 6]>(&buffer[..n])
+                .unwrap(),
+            zeros
+        );
+    }
+
+    let covered = ucpack.with_crc_covers_header(true);
+    let serialized = covered.serialize_cobs_vec(&(1u8, 2u16)).unwrap();
+    assert_ne!(serialized, ucpack.serialize_cobs_vec(&(1u8, 2u16)).unwrap());
+    assert_eq!(
+        covered
+            .deserialize_cobs_slice::<(u8, u16)>(&serialized)
+            .unwrap(),
+        (1, 2)
+    );
+
+    let crc16 = ucpack.with_checksum(Crc16::new());
+    let serialized = crc16.serialize_cobs_vec(&(1u8, 2u16)).unwrap();
+    assert_eq!(
+        crc16
+            .deserialize_cobs_slice::<(u8, u16)>(&serialized)
+            .unwrap(),
+        (1, 2)
+    );
+
+    let mut corrupted = serialized.clone();
+    corrupted[2] ^= 0x10;
+    assert!(matches!(
+        crc16.deserialize_cobs_slice::<(u8, u16)>(&corrupted),
+        Err(UcPackError::WrongCrc { .. })
+    ));
+    assert!(matches!(
+        crc16.deserialize_cobs_slice::<(u8, u16)>(&serialized[..serialized.len() - 1]),
+        Err(UcPackError::Eof { needed: 1 })
+    ));
+
+    // a stream with some garbage, an empty message and one which is too big
+    let mut stream = vec![0x11, 0x22, 0x00, 0x00];
+    stream.extend(ucpack.serialize_cobs_vec(&(1u8, 2u16)).unwrap());
+    stream.extend([0x05; 20]);
+    stream.push(0x00);
+    stream.extend(ucpack.serialize_cobs_vec(&(3u8, 4u16)).unwrap());
+
+    let mut decoder = CobsDecoder::<16>::new();
+    let messages: Vec<_> = stream
+        .iter()
+        .filter_map(|&byte| decoder.push(byte).map(<[u8]>::to_vec))
+        .collect();
+    assert_eq!(messages.len(), 3);
+    assert!(ucpack
+        .deserialize_cobs_slice::<(u8, u16)>(&messages[0])
+        .is_err());
+    assert_eq!(
+        ucpack
+            .deserialize_cobs_slice::<(u8, u16)>(&messages[1])
+            .unwrap(),
+        (1, 2)
+    );
+    assert_eq!(
+        ucpack
+            .deserialize_cobs_slice::<(u8, u16)>(&messages[2])
+            .unwrap(),
+        (3, 4)
+    );
+    assert!(decoder.is_empty());
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn test_heapless_string() {
+    use ucpack::UcPackError;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Hello {
+        id: u8,
+        name: heapless::String<32>,
+    }
+
+    let ucpack = UcPack::default();
+    let hello = Hello {
+        id: 1,
+        name: heapless::String::try_from("alvik").unwrap(),
+    };
+
+    let serialized = ucpack.serialize_vec(&hello).unwrap();
+    assert_eq!(
+        ucpack.deserialize_slice::<Hello>(&serialized).unwrap(),
+        hello
+    );
+
+    let serialized = ucpack.serialize_vec(&(1u8, "arduino alvik")).unwrap();
+    assert!(matches!(
+        ucpack.deserialize_slice::<(u8, heapless::String<4>)>(&serialized),
+        Err(UcPackError::BufferFull { needed: 13 })
+    ));
+}
+
+#[cfg(feature = "embedded-io")]
+#[test]
+fn test_embedded_io() {
+    use ucpack::buffer::{EmbeddedReadAdapter, EmbeddedWriteAdapter, WriteBuffer};
+    use ucpack::UcPackError;
+
+    let ucpack = UcPack::default();
+    let mut uart = [0u8; 16];
+    let mut tx = EmbeddedWriteAdapter(&mut uart[..], 0);
+    tx.push_slice(&ucpack.serialize_vec(&(1u8, 2u16)).unwrap())
+        .unwrap();
+    let n = tx.bytes_written();
+
+    let rx = EmbeddedReadAdapter(&uart[..n]);
+    let value: (u8, u16) = ucpack.deserialize_read(rx).unwrap();
+    assert_eq!(value, (1, 2));
+
+    let rx = EmbeddedReadAdapter(&uart[..n - 1]);
+    assert!(matches!(
+        ucpack.deserialize_read::<_, (u8, u16)>(rx),
+        Err(UcPackError::Eof { .. })
+    ));
+
+    // a faulty peripheral isn't mistaken for missing data
+    struct Overrun;
+
+    impl embedded_io::ErrorType for Overrun {
+        type Error = embedded_io::ErrorKind;
+    }
+
+    impl embedded_io::Read for Overrun {
+        fn read(&mut self, _buf: &mut [u8]) -> Result<usize, Self::Error> {
+            Err(embedded_io::ErrorKind::Other)
+        }
+    }
+
+    assert!(matches!(
+        ucpack.deserialize_read::<_, (u8, u16)>(EmbeddedReadAdapter(Overrun)),
+        Err(UcPackError::InvalidData)
+    ));
+}
+
+#[test]
+fn test_strict() {
+    use ucpack::UcPackError;
+
+    let strict = UcPack::default().with_strict(true);
+    let lenient = UcPack::default().with_strict(false);
+
+    // a legacy device ending its frames with a different index
+    let mut frame = strict.serialize_vec(&(1u8, 2u16)).unwrap();
+    let end = frame.len() - 2;
+    frame[end] = b'$';
+
+    assert!(matches!(
+        strict.deserialize_slice::<(u8, u16)>(&frame),
+        Err(UcPackError::WrongIndex { .. })
+    ));
+    assert_eq!(
+        lenient.deserialize_slice::<(u8, u16)>(&frame).unwrap(),
+        (1, 2)
+    );
+
+    // the checksum is still verified
+    frame[2] ^= 0xFF;
+    assert!(matches!(
+        lenient.deserialize_slice::<(u8, u16)>(&frame),
+        Err(UcPackError::WrongCrc { .. })
+    ));
+}
+
+#[test]
+fn test_trailing_bytes() {
+    use ucpack::UcPackError;
+
+    let ucpack = UcPack::default();
+    let frame = ucpack.serialize_vec(&(1u8, 2u16, 3u8)).unwrap();
+
+    // one byte too many for the receiving end
+    assert_eq!(
+        ucpack.deserialize_slice::<(u8, u16)>(&frame).unwrap(),
+        (1, 2)
+    );
+
+    let ucpack = ucpack.with_deny_trailing_bytes(true);
+    assert!(matches!(
+        ucpack.deserialize_slice::<(u8, u16)>(&frame),
+        Err(UcPackError::TrailingBytes { remaining: 1 })
+    ));
+    assert_eq!(
+        ucpack.deserialize_slice::<(u8, u16, u8)>(&frame).unwrap(),
+        (1, 2, 3)
+    );
+    assert_eq!(ucpack.command_code(&frame).unwrap(), 1);
+}
+
+#[test]
+fn test_wrong_crc_values() {
+    use ucpack::UcPackError;
+
+    let ucpack = UcPack::default();
+    let mut frame = ucpack.serialize_vec(&(1u8, 2u16)).unwrap();
+    let crc = *frame.last().unwrap();
+    frame[2] ^= 0x10;
+
+    let expected = ucpack::crc8_slice(&frame[2..5]);
+    match ucpack.deserialize_slice::<(u8, u16)>(&frame) {
+        Err(UcPackError::WrongCrc {
+            expected: e,
+            found: f,
+        }) => assert_eq!((e, f), (u32::from(expected), u32::from(crc))),
+        other => panic!("unexpected result {other:?}"),
+    }
+
+    // multi byte checksums are reported as big-endian integers
+    let ucpack = UcPack::default().with_checksum(ucpack::checksum::Crc16::new());
+    let mut frame = ucpack.serialize_vec(&(1u8, 2u16)).unwrap();
+    frame[2] ^= 0x10;
+
+    let err = ucpack.deserialize_slice::<(u8, u16)>(&frame).unwrap_err();
+    assert!(matches!(err, UcPackError::WrongCrc { found: 0x9DCE, .. }));
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "crc verification failed (expected {:#04x}, found 0x9dce)",
+            ucpack::checksum::crc16_slice(&frame[2..5])
+        )
+    );
+}
+
+#[test]
+fn test_builder() {
+    use ucpack::checksum::Crc16;
+
+    let ucpack = UcPack::builder()
+        .start(b'B')
+        .end(b'$')
+        .strict(true)
+        .crc(Crc16::new())
+        .extended_length(true)
+        .build();
+
+    let frame = ucpack.serialize_vec(&(1u8, 2u16)).unwrap();
+    assert_eq!(frame[..3], [b'B', 3, 0]);
+    assert_eq!(frame[6], b'$');
+    assert_eq!(frame.len(), 9);
+    assert_eq!(
+        ucpack.deserialize_slice::<(u8, u16)>(&frame).unwrap(),
+        (1, 2)
+    );
+
+    // the default builder is the default instance
+    assert_eq!(
+        UcPack::builder()
+            .build()
+            .serialize_vec(&(1u8, 2u16))
+            .unwrap(),
+        UcPack::default().serialize_vec(&(1u8, 2u16)).unwrap()
+    );
+}
+
+#[test]
+fn test_peek_header() {
+    use ucpack::{checksum::Crc16, UcPackError};
+
+    let ucpack = UcPack::default();
+    let frame = ucpack.serialize_vec(&(1u8, 2u16, 3.0f32)).unwrap();
+
+    assert!(matches!(
+        ucpack.peek_header(&[]),
+        Err(UcPackError::Eof { needed: 2 })
+    ));
+    assert!(matches!(
+        ucpack.peek_header(&frame[..1]),
+        Err(UcPackError::Eof { needed: 1 })
+    ));
+    assert!(matches!(
+        ucpack.peek_header(&frame[1..]),
+        Err(UcPackError::WrongIndex { .. })
+    ));
+
+    let header = ucpack.peek_header(&frame[..2]).unwrap();
+    assert_eq!(header.start, b'A');
+    assert_eq!(header.payload_len, 7);
+    assert_eq!(header.total_len(), frame.len());
+
+    // the overhead depends on the configuration
+    let ucpack = UcPack::default()
+        .with_extended_length(true)
+        .with_checksum(Crc16::new());
+    let frame = ucpack.serialize_vec(&(1u8, 2u16, 3.0f32)).unwrap();
+    let header = ucpack.peek_header(&frame).unwrap();
+    assert_eq!(header.payload_len, 7);
+    assert_eq!(header.total_len(), frame.len());
+}
+
+#[test]
+fn test_serialize_into() {
+    let ucpack = UcPack::default();
+    let mut buffer = Vec::with_capacity(64);
+
+    for i in 0..8u8 {
+        let payload = (i, u16::from(i) * 1000, [i; 4]);
+        ucpack.serialize_into(&payload, &mut buffer).unwrap();
+        assert_eq!(buffer, ucpack.serialize_vec(&payload).unwrap());
+    }
+    assert_eq!(buffer.capacity(), 64);
+
+    ucpack
+        .serialize_into(&[[0u8; 32]; 9], &mut buffer)
+        .unwrap_err();
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_validate_frame() {
+    use ucpack::UcPackError;
+
+    let ucpack = UcPack::default();
+    let mut stream = ucpack.serialize_vec(&(1u8, 0x0302u16)).unwrap();
+    stream.extend_from_slice(b"A\x05");
+
+    assert_eq!(ucpack.validate_frame(&stream).unwrap(), [1, 2, 3]);
+
+    let mut corrupted = stream.clone();
+    corrupted[3] ^= 0xFF;
+    assert!(matches!(
+        ucpack.validate_frame(&corrupted),
+        Err(UcPackError::WrongCrc { .. })
+    ));
+
+    assert!(matches!(
+        ucpack.validate_frame(&stream[..5]),
+        Err(UcPackError::Eof { needed: 2 })
+    ));
+}
+
+#[test]
+fn test_payload() {
+    use ucpack::UcPackError;
+
+    let value = (1u8, -2i16, 0.5f32);
+
+    for ucpack in [
+        UcPack::default(),
+        UcPack::default().with_tagged(true),
+        UcPack::default().with_extended_length(true),
+    ] {
+        let frame = ucpack.serialize_vec(&value).unwrap();
+
+        let mut buffer = [0u8; 32];
+        let n = ucpack.serialize_payload(&value, &mut buffer).unwrap();
+        assert_eq!(buffer[..n], *ucpack.validate_frame(&frame).unwrap());
+
+        let decoded: (u8, i16, f32) = ucpack.deserialize_payload(&buffer[..n]).unwrap();
+        assert_eq!(decoded, value);
+    }
+
+    let mut small = [0u8; 4];
+    assert!(matches!(
+        UcPack::default().serialize_payload(&value, &mut small),
+        Err(UcPackError::BufferFull { needed }) if needed == 3
+    ));
+}
+
+#[test]
+fn test_verify() {
+    use ucpack::{IndexKind, UcPackError};
+
+    let ucpack = UcPack::default();
+    let frame = ucpack.serialize_vec(&(1u8, 0x0302u16)).unwrap();
+    assert!(ucpack.verify(&frame).is_ok());
+
+    let mut corrupted = frame.clone();
+    *corrupted.last_mut().unwrap() ^= 0xFF;
+    assert!(matches!(
+        ucpack.verify(&corrupted),
+        Err(UcPackError::WrongCrc { .. })
+    ));
+
+    let mut wrong_end = frame.clone();
+    wrong_end[5] = b'!';
+    assert!(matches!(
+        ucpack.with_strict(true).verify(&wrong_end),
+        Err(UcPackError::WrongIndex {
+            position: IndexKind::End,
+            ..
+        })
+    ));
+    assert!(ucpack.with_strict(false).verify(&wrong_end).is_ok());
+
+    assert!(matches!(
+        ucpack.verify(&frame[..4]),
+        Err(UcPackError::Eof { needed: 3 })
+    ));
+}
+
+#[test]
+fn test_relay_payload() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Reading {
+        sensor: u8,
+        value: i16,
+    }
+
+    let node = UcPack::default();
+
+    let reading = Reading {
+        sensor: 3,
+        value: -40,
+    };
+    let frame = node.serialize_vec(&reading).unwrap();
+
+    // the gateway forwards the payload without knowing its type
+    let payload = node.validate_frame(&frame).unwrap();
+    assert_eq!(payload, [3, 0xD8, 0xFF]);
+
+    // and whoever receives it can still make sense of it
+    assert_eq!(
+        node.deserialize_payload::<Reading>(payload).unwrap(),
+        reading
+    );
+}
+
+#[test]
+fn test_non_zero() {
+    use core::num::{NonZeroI16, NonZeroU16, NonZeroU8};
+    use ucpack::UcPackError;
+
+    let ucpack = UcPack::default();
+    let value = (
+        NonZeroU8::new(1).unwrap(),
+        NonZeroU16::new(0x1234).unwrap(),
+        NonZeroI16::new(-2).unwrap(),
+    );
+
+    let serialized = ucpack.serialize_vec(&value).unwrap();
+    assert_eq!(
+        ucpack
+            .deserialize_slice::<(NonZeroU8, NonZeroU16, NonZeroI16)>(&serialized)
+            .unwrap(),
+        value
+    );
+
+    let serialized = ucpack.serialize_vec(&(1u8, 0u16)).unwrap();
+    let err = ucpack
+        .deserialize_slice::<(u8, NonZeroU16)>(&serialized)
+        .unwrap_err();
+    assert!(matches!(err.inner(), UcPackError::InvalidData));
+    assert_eq!(err.position(), Some(3));
+}
+
+#[test]
+fn test_duration() {
+    use core::time::Duration;
+    use ucpack::UcPackError;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Telemetry {
+        id: u8,
+        #[serde(with = "ucpack::time::millis")]
+        timeout: Duration,
+    }
+
+    let ucpack = UcPack::default();
+
+    // serde's own representation needs a u64, which the format lacks
+    assert!(matches!(
+        ucpack.serialize_vec(&Duration::from_secs(1)),
+        Err(UcPackError::NoSupport("u64"))
+    ));
+
+    for timeout in [
+        Duration::ZERO,
+        Duration::from_millis(1500),
+        Duration::from_millis(u32::MAX.into()),
+    ] {
+        let telemetry = Telemetry { id: 7, timeout };
+        let serialized = ucpack.serialize_vec(&telemetry).unwrap();
+        assert_eq!(serialized[1], 5);
+        assert_eq!(serialized[3..7], (timeout.as_millis() as u32).to_le_bytes());
+        assert_eq!(
+            ucpack.deserialize_slice::<Telemetry>(&serialized).unwrap(),
+            telemetry
+        );
+    }
+
+    // sub-millisecond precision is lost
+    let telemetry = Telemetry {
+        id: 7,
+        timeout: Duration::from_micros(2500),
+    };
+    let serialized = ucpack.serialize_vec(&telemetry).unwrap();
+    assert_eq!(
+        ucpack
+            .deserialize_slice::<Telemetry>(&serialized)
+            .unwrap()
+            .timeout,
+        Duration::from_millis(2)
+    );
+
+    let too_long = Telemetry {
+        id: 7,
+        timeout: Duration::from_millis(u64::from(u32::MAX) + 1),
+    };
+    assert!(matches!(
+        ucpack.serialize_vec(&too_long),
+        Err(UcPackError::SerError(_))
+    ));
+}
+
+#[test]
+fn test_frame_raw() {
+    use ucpack::{encoding::Encoding, UcPackError};
+
+    let ucpack = UcPack::default();
+    let mut buffer = [0u8; 300];
+
+    let n = ucpack.frame_raw(&[], &mut buffer).unwrap();
+    assert_eq!(buffer[..n], ucpack.serialize_vec(&[0u8; 0]).unwrap());
+    assert_eq!(buffer[..n], [b'A', 0, b'#', 0]);
+
+    let value = ([[0xAAu8; 32]; 7], [b'A'; 31]);
+    let payload = [&[0xAA; 224][..], &[b'A'; 31]].concat();
+    assert_eq!(payload.len(), 255);
+
+    let n = ucpack.frame_raw(&payload, &mut buffer).unwrap();
+    assert_eq!(n, 259);
+    assert_eq!(buffer[..n], ucpack.serialize_vec(&value).unwrap());
+    assert_eq!(ucpack.frame_raw_vec(&payload).unwrap(), buffer[..n]);
+
+    assert!(matches!(
+        ucpack.frame_raw(&[0; 256], &mut buffer),
+        Err(UcPackError::TooLong)
+    ));
+    assert!(matches!(
+        ucpack.frame_raw_vec(&[0; 256]),
+        Err(UcPackError::TooLong)
+    ));
+    assert!(matches!(
+        ucpack.frame_raw(&payload, &mut buffer[..200]),
+        Err(UcPackError::BufferFull { needed: 59 })
+    ));
+
+    let extended = ucpack.with_extended_length(true);
+    assert_eq!(
+        extended.frame_raw_vec(&[0; 256]).unwrap().len(),
+        3 + 256 + 2
+    );
+
+    // the payload is encoded just like a serialized one
+    let escaped = ucpack.with_encoding(Encoding::Escaped { escape: 0x7D });
+    let n = escaped.frame_raw(&[b'A', 2, b'#'], &mut buffer).unwrap();
+    assert_eq!(
+        buffer[..n],
+        escaped.serialize_vec(&(b'A', 2u8, b'#')).unwrap()
+    );
+}
+
+#[test]
+fn test_crc_covers_header() {
+    use ucpack::{crc8_slice, UcPackError};
+
+    let legacy = UcPack::default();
+    let covered = UcPack::builder().crc_covers_header(true).build();
+
+    // shortening the frame to its first payload byte leaves a valid end index
+    // followed by a valid crc for the legacy mode
+    let payload = (7u8, b'#', crc8_slice(&[7]), 0u8);
+
+    let mut frame = legacy.serialize_vec(&payload).unwrap();
+    assert_eq!(frame[7], crc8_slice(&[7, b'#', crc8_slice(&[7]), 0]));
+    frame[1] = 1;
+    // the legacy mode can't tell, and deserializes the wrong bytes
+    assert_eq!(legacy.validate_frame(&frame).unwrap(), [7]);
+    assert_eq!(legacy.deserialize_slice::<u8>(&frame).unwrap(), 7);
+
+    let mut buffer = [0u8; 8];
+    let n = covered.serialize_slice(&payload, &mut buffer).unwrap();
+    assert_eq!(buffer[..n], covered.serialize_vec(&payload).unwrap());
+    assert_eq!(
+        buffer[7],
+        crc8_slice(&[b'A', 4, 7, b'#', crc8_slice(&[7]), 0])
+    );
+    assert_eq!(
+        covered
+            .deserialize_slice::<(u8, u8, u8, u8)>(&buffer)
+            .unwrap(),
+        payload
+    );
+
+    buffer[1] = 1;
+    assert!(matches!(
+        covered.deserialize_slice::<u8>(&buffer),
+        Err(UcPackError::WrongCrc { .. })
+    ));
+
+    // both ends have to agree on the coverage
+    let frame = legacy.serialize_vec(&payload).unwrap();
+    assert!(covered.verify(&frame).is_err());
+}
+
+#[test]
+fn test_byte_array() {
+    use ucpack::encoding::Encoding;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Chunk {
+        offset: u16,
+        #[serde(with = "ucpack::byte_array")]
+        data: [u8; 64],
+    }
+
+    let mut chunk = Chunk {
+        offset: 0x0102,
+        data: [0; 64],
+    };
+    for (i, byte) in chunk.data.iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+
+    // same representation as a plain array
+    let ucpack = UcPack::default();
+    let halves: [[u8; 32]; 2] = [
+        chunk.data[..32].try_into().unwrap(),
+        chunk.data[32..].try_into().unwrap(),
+    ];
+    assert_eq!(
+        ucpack.serialize_vec(&chunk).unwrap(),
+        ucpack.serialize_vec(&(chunk.offset, halves)).unwrap()
+    );
+
+    for ucpack in [
+        ucpack,
+        // tagged arrays are read one byte at a time
+        ucpack.with_tagged(true),
+        // the payload is decoded on the stack, so the array is copied
+        ucpack.with_encoding(Encoding::Escaped { escape: 0x7D }),
+    ] {
+        let serialized = ucpack.serialize_vec(&chunk).unwrap();
+        assert_eq!(
+            ucpack.deserialize_slice::<Chunk>(&serialized).unwrap(),
+            chunk
+        );
+    }
+
+    // arrays too long to be read at once
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Image(#[serde(with = "ucpack::byte_array")] [u8; 300]);
+
+    let ucpack = UcPack::default().with_extended_length(true);
+    let serialized = ucpack.serialize_vec(&Image([0xAA; 300])).unwrap();
+    assert_eq!(serialized.len(), 3 + 300 + 2);
+    assert_eq!(
+        ucpack.deserialize_slice::<Image>(&serialized).unwrap(),
+        Image([0xAA; 300])
+    );
+
+    let short = ucpack.serialize_vec(&[0xAAu8; 30]).unwrap();
+    assert!(ucpack.deserialize_slice::<Chunk>(&short).is_err());
+}
+
+#[test]
+fn test_cow() {
+    use std::borrow::Cow;
+    use ucpack::encoding::Encoding;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Log<'a> {
+        level: u8,
+        #[serde(borrow)]
+        message: Cow<'a, str>,
+    }
+
+    let log = Log {
+        level: 2,
+        message: Cow::Owned("motor stalled".to_string()),
+    };
+
+    // borrowed straight from the buffer
+    let ucpack = UcPack::default();
+    let serialized = ucpack.serialize_vec(&log).unwrap();
+    let decoded: Log<'_> = ucpack.deserialize_slice(&serialized).unwrap();
+    assert_eq!(decoded, log);
+    assert!(matches!(decoded.message, Cow::Borrowed("motor stalled")));
+
+    // the payload is decoded on the stack, so it can't be borrowed from
+    let escaped = ucpack.with_encoding(Encoding::Escaped { escape: 0x7D });
+    let serialized = escaped.serialize_vec(&log).unwrap();
+    let decoded: Log<'_> = escaped.deserialize_slice(&serialized).unwrap();
+    assert_eq!(decoded, log);
+    assert!(matches!(decoded.message, Cow::Owned(_)));
+}
+
+#[test]
+fn test_empty_frame() {
+    use ucpack::decoder::Decoder;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Heartbeat;
+
+    let ucpack = UcPack::default();
+    assert_eq!(ucpack::crc8_slice(&[]), 0x00);
+
+    let frame = ucpack.serialize_vec(&()).unwrap();
+    assert_eq!(frame, [b'A', 0, b'#', 0x00]);
+    assert_eq!(ucpack.serialize_vec(&Heartbeat).unwrap(), frame);
+    assert_eq!(ucpack.serialize_size(&Heartbeat).unwrap(), 4);
+
+    assert_eq!(ucpack.is_complete_message(&frame).unwrap(), frame);
+    assert!(ucpack.is_complete_message(&frame[..3]).is_err());
+    assert_eq!(ucpack.peek_header(&frame).unwrap().total_len(), 4);
+    ucpack.deserialize_slice::<()>(&frame).unwrap();
+    assert_eq!(
+        ucpack.deserialize_slice::<Heartbeat>(&frame).unwrap(),
+        Heartbeat
+    );
+
+    let mut decoder = Decoder::<8>::new(ucpack);
+    let stream = [&frame[..], &frame].concat();
+    let received = stream.iter().filter(|&&byte| decoder.push(byte).is_some());
+    assert_eq!(received.count(), 2);
+
+    // units are tagged as well in the tagged format
+    let tagged = ucpack.with_tagged(true);
+    let frame = tagged.serialize_vec(&Heartbeat).unwrap();
+    assert_eq!(frame[1..3], [1, ucpack::tag::UNIT]);
+    assert_eq!(
+        tagged.deserialize_slice::<Heartbeat>(&frame).unwrap(),
+        Heartbeat
+    );
+}
+
+#[test]
+fn test_frame_overhead() {
+    use ucpack::checksum::{Checksum, Crc16};
+
+    assert_eq!(UcPack::FRAME_OVERHEAD, 4);
+    assert_eq!(UcPack::MAX_PAYLOAD, 255);
+    assert_eq!(UcPack::MAX_FRAME_SIZE, 259);
+
+    let ucpack = UcPack::default();
+    assert_eq!(ucpack.overhead(), UcPack::FRAME_OVERHEAD);
+    assert_eq!(ucpack.max_payload_size(), UcPack::MAX_PAYLOAD);
+    assert_eq!(ucpack.max_frame_size(), UcPack::MAX_FRAME_SIZE);
+
+    let biggest = ([[0u8; 32]; 7], [0u8; 31]);
+    assert_eq!(
+        ucpack.serialize_vec(&biggest).unwrap().len(),
+        UcPack::MAX_FRAME_SIZE
+    );
+    assert_eq!(
+        ucpack.serialize_vec(&()).unwrap().len(),
+        UcPack::FRAME_OVERHEAD
+    );
+
+    fn check<C: Checksum>(ucpack: UcPack<C>, overhead: usize) {
+        assert_eq!(ucpack.overhead(), overhead);
+        for payload_len in [0, 1, 3] {
+            let payload = [0xAAu8; 3];
+            let frame = ucpack.frame_raw_vec(&payload[..payload_len]).unwrap();
+            assert_eq!(frame.len(), ucpack.frame_size(payload_len));
+        }
+    }
+
+    check(ucpack, 4);
+    check(ucpack.with_checksum(Crc16::new()), 5);
+    check(ucpack.with_extended_length(true), 5);
+
+    let extended = ucpack.with_extended_length(true);
+    assert_eq!(extended.max_payload_size(), 65535);
+    assert_eq!(extended.max_frame_size(), 65535 + 5);
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn test_arbitrary_raw_frame() {
+    use arbitrary::{Arbitrary, Unstructured};
+    use ucpack::{fuzz::RawFrame, UcPackError};
+
+    let ucpack = UcPack::default();
+    let data: Vec<u8> = (0..4096u32).map(|i| ((i * 7919) >> 3) as u8).collect();
+    let mut data = Unstructured::new(&data);
+
+    let (mut valid, mut corrupted) = (0, 0);
+    while !data.is_empty() {
+        let RawFrame(frame) = RawFrame::arbitrary(&mut data).unwrap();
+
+        // the structure is always right
+        assert_eq!(frame[0], b'A');
+        assert_eq!(frame.len(), ucpack.frame_size(frame[1] as usize));
+        assert_eq!(frame[frame.len() - 2], b'#');
+
+        match ucpack.verify(&frame) {
+            Ok(()) => valid += 1,
+            Err(UcPackError::WrongCrc { .. }) => corrupted += 1,
+            Err(err) => panic!("unexpected error {err:?}"),
+        }
+    }
+
+    assert!(valid > 0 && corrupted > 0);
+}
+
+#[test]
+fn test_fragmentation() {
+    use ucpack::{encoding::Encoding, fragment::Reassembler, UcPackError};
+
+    let mut blob = [[0u8; 32]; 32];
+    for (i, row) in blob.iter_mut().enumerate() {
+        for (j, byte) in row.iter_mut().enumerate() {
+            *byte = (i * 32 + j) as u8;
+        }
+    }
+
+    for ucpack in [
+        UcPack::default(),
+        UcPack::default().with_encoding(Encoding::Escaped { escape: 0x7D }),
+    ] {
+        let mut frames = Vec::new();
+        let n = ucpack
+            .serialize_fragmented(7, &blob, |frame| frames.push(frame.to_vec()))
+            .unwrap();
+        assert_eq!(n, frames.len());
+        assert!(n > 1);
+
+        let mut reassembler = Reassembler::<1024, _>::new(ucpack);
+        for frame in &frames[..n - 1] {
+            assert!(reassembler.push(frame).unwrap().is_none());
+        }
+        let payload = reassembler.push(&frames[n - 1]).unwrap().unwrap();
+        assert_eq!(payload.len(), 1024);
+        assert_eq!(
+            ucpack
+                .deserialize_payload::<[[u8; 32]; 32]>(payload)
+                .unwrap(),
+            blob
+        );
+        assert!(reassembler.is_empty());
+
+        // a missing fragment
+        assert!(reassembler.push(&frames[0]).unwrap().is_none());
+        assert!(matches!(
+            reassembler.push(&frames[2]),
+            Err(UcPackError::WrongFragment {
+                expected: 1,
+                found: 2
+            })
+        ));
+        assert!(reassembler.is_empty());
+
+        // the rest of the message is rejected as well, rather than reassembled
+        for frame in &frames[3..] {
+            assert!(matches!(
+                reassembler.push(frame),
+                Err(UcPackError::WrongFragment { expected: 0, .. })
+            ));
+        }
+    }
+
+    // fragments of another message
+    let ucpack = UcPack::default();
+    let mut first = Vec::new();
+    let mut second = Vec::new();
+    ucpack
+        .serialize_fragmented(1, &blob, |frame| first.push(frame.to_vec()))
+        .unwrap();
+    ucpack
+        .serialize_fragmented(2, &blob, |frame| second.push(frame.to_vec()))
+        .unwrap();
+
+    let mut reassembler = Reassembler::<1024>::new(ucpack);
+    assert!(reassembler.push(&first[0]).unwrap().is_none());
+    assert!(matches!(
+        reassembler.push(&second[1]),
+        Err(UcPackError::WrongFragment {
+            expected: 0,
+            found: 1
+        })
+    ));
+
+    // a message too big for the reassembler
+    let mut reassembler = Reassembler::<512>::new(ucpack);
+    let result = first
+        .iter()
+        .map(|frame| reassembler.push(frame).map(|payload| payload.is_some()))
+        .find(Result::is_err);
+    assert!(matches!(result, Some(Err(UcPackError::BufferFull { .. }))));
+
+    // single, empty fragment
+    let mut frames = Vec::new();
+    assert_eq!(
+        ucpack
+            .serialize_fragmented(3, &(), |frame| frames.push(frame.to_vec()))
+            .unwrap(),
+        1
+    );
+    let mut reassembler = Reassembler::<8>::new(ucpack);
+    assert_eq!(reassembler.push(&frames[0]).unwrap(), Some(&[][..]));
+}
+
+#[cfg(feature = "proptest")]
+mod roundtrip {
+    use proptest::prelude::*;
+    use ucpack::testing::{command, payload, roundtrip, ucpack};
+
+    proptest! {
+        #[test]
+        fn test_roundtrip(ucpack in ucpack(), payload in payload()) {
+            roundtrip(&ucpack, &payload)?;
+        }
+
+        #[test]
+        fn test_roundtrip_option(ucpack in ucpack(), command in proptest::option::of(command())) {
+            roundtrip(&ucpack.with_tagged(true), &command)?;
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use bytes::BytesMut;
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::{Decoder, Encoder};
+use ucpack::{codec::UcPackCodec, UcPack, UcPackError};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestPayload {
+    left: i16,
+    right: i16,
+    enabled: bool,
+}
+
+#[test]
+fn test_codec_partial_frames() {
+    let payload = TestPayload {
+        left: -300,
+        right: 300,
+        enabled: true,
+    };
+
+    let mut codec = UcPackCodec::<TestPayload>::default();
+    let mut encoded = BytesMut::new();
+    codec.encode(payload, &mut encoded).unwrap();
+    codec
+        .encode(
+            TestPayload {
+                left: 1,
+                right: 2,
+                enabled: false,
+            },
+            &mut encoded,
+        )
+        .unwrap();
+
+    let frame_len = encoded.len() / 2;
+    let mut src = BytesMut::new();
+
+    // nothing, then the start index only, then everything but the crc
+    assert!(codec.decode(&mut src).unwrap().is_none());
+    src.extend_from_slice(&encoded[..1]);
+    assert!(codec.decode(&mut src).unwrap().is_none());
+    src.extend_from_slice(&encoded[1..frame_len - 1]);
+    assert!(codec.decode(&mut src).unwrap().is_none());
+    assert_eq!(src.len(), frame_len - 1);
+
+    // the first frame completes, along with part of the second one
+    src.extend_from_slice(&encoded[frame_len - 1..frame_len + 3]);
+    assert_eq!(
+        codec.decode(&mut src).unwrap(),
+        Some(TestPayload {
+            left: -300,
+            right: 300,
+            enabled: true,
+        })
+    );
+    assert_eq!(src.len(), 3);
+    assert!(codec.decode(&mut src).unwrap().is_none());
+
+    src.extend_from_slice(&encoded[frame_len + 3..]);
+    assert_eq!(
+        codec.decode(&mut src).unwrap(),
+        Some(TestPayload {
+            left: 1,
+            right: 2,
+            enabled: false,
+        })
+    );
+    assert!(src.is_empty());
+}
+
+#[test]
+fn test_codec_bad_crc() {
+    let mut codec = UcPackCodec::<(u8, u8)>::new(UcPack::default());
+    let mut src = BytesMut::new();
+    codec.encode((1, 2), &mut src).unwrap();
+    codec.encode((3, 4), &mut src).unwrap();
+
+    let crc_index = src.len() / 2 - 1;
+    src[crc_index] ^= 0xFF;
+
+    // the corrupted frame is skipped, leaving the next one intact
+    assert!(matches!(
+        codec.decode(&mut src),
+        Err(UcPackError::WrongCrc { .. })
+    ));
+    assert_eq!(codec.decode(&mut src).unwrap(), Some((3, 4)));
+}
+
+#[test]
+fn test_codec_noise() {
+    let mut codec = UcPackCodec::<(u8, u8)>::new(UcPack::default());
+    let mut frame = BytesMut::new();
+    codec.encode((1, 2), &mut frame).unwrap();
+
+    // noise, a start index followed by a bogus frame, then a whole frame
+    let mut src = BytesMut::from(&[0x55, b'A', 0x01, 0x00][..]);
+    src.extend_from_slice(&frame);
+    assert_eq!(codec.decode(&mut src).unwrap(), Some((1, 2)));
+    assert!(src.is_empty());
+
+    // noise alone is dropped while waiting for a frame
+    src.extend_from_slice(&[0x55, 0x66]);
+    assert!(codec.decode(&mut src).unwrap().is_none());
+    assert!(src.is_empty());
+
+    let mut ucpack = UcPack::default();
+    src.extend_from_slice(&[0x55, b'A', 1, 1]);
+    src.extend_from_slice(&frame);
+    assert_eq!(ucpack.decode(&mut src).unwrap(), Some(frame));
+}
+
+#[test]
+fn test_codec_encode_error() {
+    let mut codec = UcPackCodec::<[[u16; 32]; 4]>::default();
+    let mut dst = BytesMut::from(&b"ab"[..]);
+
+    assert!(matches!(
+        codec.encode([[0; 32]; 4], &mut dst),
+        Err(UcPackError::TooLong)
+    ));
+    assert_eq!(&dst[..], b"ab");
+}
+
+#[tokio::test]
+async fn test_duplex_adapters() {
+    use ucpack::asynch::{TokioReadAdapter, TokioWriteAdapter};
+
+    let ucpack = UcPack::default();
+    let (client, server) = tokio::io::duplex(64);
+    let mut tx = TokioWriteAdapter(client);
+    let mut rx = TokioReadAdapter(server);
+
+    let payload = TestPayload {
+        left: -300,
+        right: 300,
+        enabled: true,
+    };
+    let sent = ucpack.serialize_async(&payload, &mut tx).await.unwrap();
+    assert_eq!(sent, ucpack.serialize_size(&payload).unwrap());
+
+    let received: TestPayload = ucpack.deserialize_async(&mut rx).await.unwrap();
+    assert_eq!(received, payload);
+
+    // the other end hung up mid-frame
+    let frame = ucpack.serialize_vec(&payload).unwrap();
+    tokio::io::AsyncWriteExt::write_all(&mut tx.0, &frame[..4])
+        .await
+        .unwrap();
+    drop(tx);
+    assert!(matches!(
+        ucpack.deserialize_async::<TestPayload>(&mut rx).await,
+        Err(UcPackError::Eof { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_framed_ucpack() {
+    use futures::{SinkExt, StreamExt};
+    use tokio_util::codec::Framed;
+
+    let (client, server) = tokio::io::duplex(64);
+    let mut tx = Framed::new(client, UcPack::default());
+    let mut rx = Framed::new(server, UcPack::default());
+
+    let payload = TestPayload {
+        left: -300,
+        right: 300,
+        enabled: true,
+    };
+    tx.send(&payload).await.unwrap();
+    tx.send((3u8, 4u8)).await.unwrap();
+
+    let frame = rx.next().await.unwrap().unwrap();
+    let received: TestPayload = rx.codec().deserialize_slice(&frame).unwrap();
+    assert_eq!(received, payload);
+
+    let frame = rx.next().await.unwrap().unwrap();
+    assert_eq!(
+        rx.codec().deserialize_slice::<(u8, u8)>(&frame).unwrap(),
+        (3, 4)
+    );
+
+    drop(tx);
+    assert!(rx.next().await.is_none());
+}